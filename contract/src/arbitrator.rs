@@ -0,0 +1,122 @@
+use near_sdk::{env, ext_contract, near_bindgen, Gas, PromiseError};
+
+use crate::dispute::record_resolution;
+use crate::types::*;
+use crate::{Contract, ContractExt};
+
+const ARBITRATION_REQUEST_GAS: Gas = Gas::from_tgas(30);
+const ARBITRATION_CALLBACK_GAS: Gas = Gas::from_tgas(20);
+
+/// Interface an external arbitrator contract must implement. It may answer
+/// right away through the return value, or return `None` and call
+/// `submit_arbitrator_resolution` once it has decided.
+#[allow(dead_code)]
+#[ext_contract(ext_arbitrator)]
+pub trait ExternalArbitrator {
+    fn request_arbitration(&mut self, request: ArbitrationRequest) -> Option<ArbitrationDecision>;
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn request_arbitration(&mut self, contract_id: String, milestone_id: String) {
+        self.require_not_paused(PauseScope::AiResolutions);
+        let caller = env::predecessor_account_id();
+        let contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        assert!(contract.is_party(&caller), "Only contract parties can request arbitration");
+
+        let arbitrator_contract = match &contract.arbitrator {
+            Arbitrator::External { contract_id } => contract_id.clone(),
+            _ => env::panic_str("Contract does not use an external arbitrator"),
+        };
+
+        let dispute_idx = contract
+            .find_dispute(&milestone_id, DisputeStatus::Pending)
+            .expect("No active dispute for this milestone");
+        let dispute = &contract.disputes[dispute_idx];
+        assert!(
+            dispute.response_window_closed_at(env::block_timestamp()),
+            "Dispute is still in its response window"
+        );
+        let milestone_idx = contract.find_milestone(&milestone_id).expect("Milestone not found");
+
+        let request = ArbitrationRequest {
+            escrow_contract_id: contract_id.clone(),
+            milestone_id: milestone_id.clone(),
+            raised_by: dispute.raised_by.clone(),
+            reason: dispute.reason.clone(),
+            category: dispute.category.clone(),
+            claimed_remedy: dispute.claimed_remedy.clone(),
+            amount: contract.milestones[milestone_idx].amount,
+            prompt_hash: contract.prompt_hash.clone(),
+        };
+
+        let _ = ext_arbitrator::ext(arbitrator_contract.clone())
+            .with_static_gas(ARBITRATION_REQUEST_GAS)
+            .request_arbitration(request)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(ARBITRATION_CALLBACK_GAS)
+                    .on_arbitration_response(contract_id.clone(), milestone_id.clone()),
+            );
+
+        emit_event!("arbitration_requested", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "arbitrator" => arbitrator_contract
+        });
+    }
+
+    #[private]
+    pub fn on_arbitration_response(
+        &mut self,
+        contract_id: String,
+        milestone_id: String,
+        #[callback_result] response: Result<Option<ArbitrationDecision>, PromiseError>,
+    ) {
+        let decision = match response {
+            Ok(Some(_)) if self.is_paused(PauseScope::AiResolutions) => {
+                emit_event!("arbitration_request_failed", {
+                    "contract_id" => contract_id,
+                    "milestone_id" => milestone_id
+                });
+                return;
+            }
+            Ok(Some(decision)) => decision,
+            Ok(None) => return,
+            Err(_) => {
+                emit_event!("arbitration_request_failed", {
+                    "contract_id" => contract_id,
+                    "milestone_id" => milestone_id
+                });
+                return;
+            }
+        };
+
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        let Some(dispute_idx) = contract.find_dispute(&milestone_id, DisputeStatus::Pending) else {
+            return;
+        };
+
+        let invalid = matches!(
+            decision.resolution,
+            Resolution::Split { freelancer_pct } if freelancer_pct > 100
+        ) || !contract.resolution_within_claim(dispute_idx, &decision.resolution);
+        if invalid {
+            emit_event!("arbitration_request_failed", {
+                "contract_id" => contract_id,
+                "milestone_id" => milestone_id
+            });
+            return;
+        }
+
+        let arbitrator = contract.arbitrator.account_id().cloned().expect("No arbitrator account");
+        record_resolution(&mut contract, dispute_idx, decision.resolution, decision.explanation);
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("arbitrator_resolution", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "arbitrator" => arbitrator
+        });
+    }
+}
@@ -1,10 +1,57 @@
-use near_sdk::{env, near_bindgen, NearToken, Promise};
+use near_sdk::json_types::U64;
+use near_sdk::{env, near_bindgen, AccountId, NearToken, Promise};
 
+use crate::evidence::{evidence_root, to_hex, EVIDENCE_WINDOW_NS};
+use crate::milestone::OVERDUE_GRACE_NS;
+use crate::statement::RESPONSE_WINDOW_NS;
 use crate::types::*;
 use crate::{Contract, ContractExt};
 
-const DISPUTE_DEADLINE_NS: u64 = 48 * 60 * 60 * 1_000_000_000;
-const PAYMENT_COOLDOWN_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+pub(crate) const DISPUTE_DEADLINE_NS: u64 = 48 * 60 * 60 * 1_000_000_000;
+pub(crate) const PAYMENT_COOLDOWN_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+const DEFAULT_LEASE_NS: u64 = 10 * 60 * 1_000_000_000;
+const MAX_LEASE_NS: u64 = 60 * 60 * 1_000_000_000;
+const MAX_OVERRIDES_PER_MILESTONE: u8 = 2;
+const OVERRIDE_COMPENSATION_PCT: u128 = 10;
+pub(crate) const MAX_AI_PROCESSING_FEE: NearToken = NearToken::from_near(1);
+pub(crate) const DEFAULT_AI_RESOLUTION_TIMEOUT_NS: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+const ESCALATION_TIMEOUT_NS: u64 = 14 * 24 * 60 * 60 * 1_000_000_000;
+
+/// Stores a ruling from any arbitrator. `ContinueWork` takes effect immediately;
+/// every other resolution opens the review window. Rulings may not refund the
+/// client more than the remedy they claimed.
+pub(crate) fn record_resolution(
+    contract: &mut EscrowContract,
+    dispute_idx: usize,
+    resolution: Resolution,
+    explanation: String,
+) {
+    assert!(
+        contract.resolution_within_claim(dispute_idx, &resolution),
+        "Resolution exceeds the remedy claimed by the client"
+    );
+    let milestone_id = contract.disputes[dispute_idx].milestone_id.clone();
+    contract.disputes[dispute_idx].resolution = Some(resolution.clone());
+    contract.disputes[dispute_idx].explanation = Some(explanation);
+    contract.disputes[dispute_idx].resolved_at_ns = Some(env::block_timestamp());
+
+    match resolution {
+        Resolution::ContinueWork => {
+            contract.disputes[dispute_idx].status = DisputeStatus::Finalized;
+            contract.disputes[dispute_idx].funds_released = true;
+
+            let milestone_idx = contract.find_milestone(&milestone_id).expect("Milestone not found");
+            contract.milestones[milestone_idx].status = MilestoneStatus::InProgress;
+            contract.milestones[milestone_idx].payment_request_deadline_ns = None;
+            contract.status = ContractStatus::Active;
+        }
+        _ => {
+            contract.disputes[dispute_idx].status = DisputeStatus::AiResolved;
+            contract.disputes[dispute_idx].deadline_ns =
+                Some(env::block_timestamp() + contract.time_windows.dispute_review_ns);
+        }
+    }
+}
 
 #[near_bindgen]
 impl Contract {
@@ -13,21 +60,32 @@ impl Contract {
         contract_id: String,
         milestone_id: String,
         reason: String,
+        category: DisputeCategory,
+        claimed_remedy: ClaimedRemedy,
     ) {
         let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        contract.require_not_on_hold();
         let caller = env::predecessor_account_id();
         assert_eq!(caller, contract.client, "Only client can raise disputes");
 
         assert!(
-            contract.security_pool.as_yoctonear() >= self.ai_processing_fee.as_yoctonear(),
+            contract.security_pool >= contract.ai_fee,
             "Insufficient security deposit for AI processing"
         );
 
         let idx = contract.find_milestone(&milestone_id).expect("Milestone not found");
+        let overdue = category == DisputeCategory::NonDelivery
+            && contract.milestones[idx].is_overdue_at(env::block_timestamp(), OVERDUE_GRACE_NS);
         assert!(
-            contract.milestones[idx].status == MilestoneStatus::SubmittedForReview,
+            contract.milestones[idx].status == MilestoneStatus::SubmittedForReview || overdue,
             "Can only dispute milestones submitted for review"
         );
+        if let ClaimedRemedy::PartialRefund { amount } = &claimed_remedy {
+            assert!(
+                amount.0 > 0 && amount.0 <= contract.milestones[idx].amount.as_yoctonear(),
+                "Partial refund must be between zero and the milestone amount"
+            );
+        }
 
         contract.milestones[idx].status = MilestoneStatus::Disputed;
         contract.status = ContractStatus::Disputed;
@@ -36,22 +94,165 @@ impl Contract {
             milestone_id: milestone_id.clone(),
             raised_by: caller,
             reason,
+            category: category.clone(),
+            claimed_remedy,
             status: DisputeStatus::Pending,
             resolution: None,
             explanation: None,
             deadline_ns: None,
             ai_fee_deducted: false,
+            ai_fee_amount: NearToken::from_yoctonear(0),
+            ai_fee_operator: None,
+            ai_fee_operator_share: NearToken::from_yoctonear(0),
             tee_signature: None,
             tee_signing_address: None,
             tee_text: None,
             funds_released: false,
+            client_accepted: false,
+            freelancer_accepted: false,
+            settlement: None,
+            resolution_deadline_ns: Some(env::block_timestamp() + self.ai_resolution_timeout_ns),
+            escalated_to: None,
+            fallback_applied: false,
+            jury: None,
+            resolved_at_ns: None,
+            lease: None,
+            evidence: vec![],
+            evidence_deadline_ns: Some(env::block_timestamp() + EVIDENCE_WINDOW_NS),
+            statements: vec![],
+            response_deadline_ns: Some(env::block_timestamp() + RESPONSE_WINDOW_NS),
+            client_waived_response: false,
+            freelancer_waived_response: false,
         });
 
         self.contracts.insert(contract_id.clone(), contract);
 
         emit_event!("dispute_raised", {
             "contract_id" => contract_id,
-            "milestone_id" => milestone_id
+            "milestone_id" => milestone_id,
+            "category" => format!("{:?}", category)
+        });
+    }
+
+    /// Withdraws a pending dispute. The milestone either goes back to review with a
+    /// fresh deadline (or to work, if nothing was submitted) or, with `approve`, is
+    /// paid out to the freelancer. The AI fee
+    /// is only deducted on resolution, so the security pool is left as is.
+    pub fn withdraw_dispute(&mut self, contract_id: String, milestone_id: String, approve: bool) {
+        if approve {
+            self.require_not_paused(PauseScope::Releases);
+        }
+        let caller = env::predecessor_account_id();
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+
+        let dispute_idx = contract
+            .find_dispute(&milestone_id, DisputeStatus::Pending)
+            .expect("No active dispute for this milestone");
+        assert_eq!(
+            caller, contract.disputes[dispute_idx].raised_by,
+            "Only the party who raised the dispute can withdraw it"
+        );
+
+        contract.disputes[dispute_idx].status = DisputeStatus::Withdrawn;
+
+        let milestone_idx = contract.find_milestone(&milestone_id).expect("Milestone not found");
+        let amount = contract.milestones[milestone_idx].amount;
+        if approve {
+            contract.milestones[milestone_idx].status = MilestoneStatus::Completed;
+            contract.milestones[milestone_idx].payment_request_deadline_ns = None;
+        } else if contract.milestones[milestone_idx].payment_request_deadline_ns.is_none() {
+            // A non-delivery dispute on an overdue milestone: nothing was submitted.
+            contract.milestones[milestone_idx].status = MilestoneStatus::InProgress;
+        } else {
+            contract.milestones[milestone_idx].status = MilestoneStatus::SubmittedForReview;
+            contract.milestones[milestone_idx].payment_request_deadline_ns =
+                Some(env::block_timestamp() + contract.time_windows.payment_review_ns);
+        }
+        contract.restore_status();
+
+        let platform_fee = if approve {
+            self.disburse(&contract, amount, NearToken::from_yoctonear(0))
+        } else {
+            NearToken::from_yoctonear(0)
+        };
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("dispute_withdrawn", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "approved" => approve,
+            "platform_fee" => platform_fee.as_yoctonear()
+        });
+    }
+
+    /// Leases a pending dispute to one agent so others polling `get_pending_disputes`
+    /// skip it. The caller must operate `agent_key`; an expired lease can be claimed
+    /// by anyone, and the holder can renew its own lease.
+    pub fn claim_dispute(
+        &mut self,
+        contract_id: String,
+        milestone_id: String,
+        agent_key: Vec<u8>,
+        duration_ns: Option<U64>,
+    ) {
+        let caller = env::predecessor_account_id();
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        assert!(
+            contract.arbitrator == Arbitrator::Tee,
+            "Contract does not use TEE arbitration"
+        );
+
+        let now = env::block_timestamp();
+        let tee_key = self
+            .find_tee_key(&agent_key)
+            .expect("Signing address not in trusted TEE list");
+        assert_eq!(tee_key.operator, caller, "Only the key's operator can claim with it");
+        assert!(tee_key.is_valid_at(now), "TEE key is outside its validity window");
+        assert!(
+            tee_key.allows_model(&contract.model_id),
+            "TEE key is not allowed for this contract's model"
+        );
+
+        let duration_ns = duration_ns.map(|d| d.0).unwrap_or(DEFAULT_LEASE_NS);
+        assert!(
+            duration_ns > 0 && duration_ns <= MAX_LEASE_NS,
+            "Lease duration out of range"
+        );
+
+        let dispute_idx = contract
+            .find_dispute(&milestone_id, DisputeStatus::Pending)
+            .expect("No active dispute for this milestone");
+        let dispute = &mut contract.disputes[dispute_idx];
+        assert!(
+            dispute.response_window_closed_at(now),
+            "Dispute is still in its response window"
+        );
+        if let Some(lease) = &dispute.lease {
+            assert!(
+                lease.agent_key == agent_key || now >= lease.expires_at_ns,
+                "Dispute is already claimed"
+            );
+        }
+
+        let expires_at_ns = now + duration_ns;
+        // Record the evidence set the agent is about to review; it is already frozen.
+        let evidence_root = evidence_root(&dispute.evidence)
+            .map(|root| to_hex(&root))
+            .unwrap_or_default();
+        dispute.lease = Some(DisputeLease {
+            agent_key,
+            operator: caller.clone(),
+            expires_at_ns,
+        });
+
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("dispute_claimed", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "operator" => caller,
+            "expires_at_ns" => expires_at_ns,
+            "evidence_root" => evidence_root
         });
     }
 
@@ -65,6 +266,13 @@ impl Contract {
         signing_address: Vec<u8>,
         tee_text: String,
     ) {
+        self.require_not_paused(PauseScope::AiResolutions);
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        assert!(
+            contract.arbitrator == Arbitrator::Tee,
+            "Contract does not use TEE arbitration"
+        );
+
         let sig: &[u8; 64] = signature
             .as_slice()
             .try_into()
@@ -77,53 +285,59 @@ impl Contract {
             env::ed25519_verify(sig, tee_text.as_bytes(), pubkey),
             "Invalid TEE signature"
         );
+        let tee_key = self
+            .find_tee_key(&signing_address)
+            .expect("Signing address not in trusted TEE list");
+        let operator = tee_key.operator.clone();
+        assert!(
+            tee_key.is_valid_at(env::block_timestamp()),
+            "TEE key is outside its validity window"
+        );
         assert!(
-            self.trusted_tee_addresses.contains(&signing_address),
-            "Signing address not in trusted TEE list"
+            tee_key.allows_model(&contract.model_id),
+            "TEE key is not allowed for this contract's model"
         );
 
         if let Resolution::Split { freelancer_pct } = &resolution {
             assert!(*freelancer_pct <= 100, "Invalid split percentage");
         }
 
-        let fee = self.ai_processing_fee;
-        let owner = self.owner.clone();
-
-        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
-
         let dispute_idx = contract
             .find_dispute(&milestone_id, DisputeStatus::Pending)
             .expect("No active dispute for this milestone");
 
+        if let Some(lease) = &contract.disputes[dispute_idx].lease {
+            assert!(
+                lease.agent_key == signing_address || env::block_timestamp() >= lease.expires_at_ns,
+                "Dispute is claimed by another agent"
+            );
+        }
+        contract.disputes[dispute_idx].lease = None;
+        // Evidence closes with the response window, so the set is fixed by now.
+        assert!(
+            contract.disputes[dispute_idx].response_window_closed_at(env::block_timestamp()),
+            "Dispute is still in its response window"
+        );
+
+        // The fee was fixed when the escrow was created, and other disputes may have
+        // drained the pool since this one was raised, so never charge more than it holds.
+        let fee = contract.ai_fee.min(contract.security_pool);
+
         if !contract.disputes[dispute_idx].ai_fee_deducted && fee.as_yoctonear() > 0 {
             contract.security_pool = NearToken::from_yoctonear(
-                contract.security_pool.as_yoctonear().saturating_sub(fee.as_yoctonear()),
+                contract.security_pool.as_yoctonear() - fee.as_yoctonear(),
             );
-            let _ = Promise::new(owner).transfer(fee);
             contract.disputes[dispute_idx].ai_fee_deducted = true;
+            contract.disputes[dispute_idx].ai_fee_amount = fee;
+            contract.disputes[dispute_idx].ai_fee_operator = Some(operator.clone());
         }
 
-        contract.disputes[dispute_idx].resolution = Some(resolution.clone());
-        contract.disputes[dispute_idx].explanation = Some(explanation);
         contract.disputes[dispute_idx].tee_signature = Some(signature);
         contract.disputes[dispute_idx].tee_signing_address = Some(signing_address);
         contract.disputes[dispute_idx].tee_text = Some(tee_text);
-
-        match resolution {
-            Resolution::ContinueWork => {
-                contract.disputes[dispute_idx].status = DisputeStatus::Finalized;
-                contract.disputes[dispute_idx].funds_released = true;
-
-                let milestone_idx = contract.find_milestone(&milestone_id).expect("Milestone not found");
-                contract.milestones[milestone_idx].status = MilestoneStatus::InProgress;
-                contract.milestones[milestone_idx].payment_request_deadline_ns = None;
-                contract.status = ContractStatus::Active;
-            }
-            _ => {
-                contract.disputes[dispute_idx].status = DisputeStatus::AiResolved;
-                contract.disputes[dispute_idx].deadline_ns =
-                    Some(env::block_timestamp() + DISPUTE_DEADLINE_NS);
-            }
+        record_resolution(&mut contract, dispute_idx, resolution, explanation);
+        if contract.disputes[dispute_idx].funds_released {
+            self.pay_ai_fee(&mut contract.disputes[dispute_idx]);
         }
 
         self.contracts.insert(contract_id.clone(), contract);
@@ -139,18 +353,265 @@ impl Contract {
         let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
 
         assert!(contract.is_party(&caller), "Only contract parties can accept");
+        let is_client = caller == contract.client;
 
         let dispute_idx = contract
             .find_dispute(&milestone_id, DisputeStatus::AiResolved)
             .expect("No resolved dispute to accept");
 
-        contract.disputes[dispute_idx].status = DisputeStatus::Finalized;
+        let dispute = &mut contract.disputes[dispute_idx];
+        if is_client {
+            assert!(!dispute.client_accepted, "Resolution already accepted");
+            dispute.client_accepted = true;
+        } else {
+            assert!(!dispute.freelancer_accepted, "Resolution already accepted");
+            dispute.freelancer_accepted = true;
+        }
+
+        let finalized = dispute.client_accepted && dispute.freelancer_accepted;
+        if finalized {
+            dispute.status = DisputeStatus::Finalized;
+        }
 
         self.contracts.insert(contract_id.clone(), contract);
 
-        emit_event!("dispute_finalized", {
+        emit_event!("resolution_accepted", {
             "contract_id" => contract_id,
-            "milestone_id" => milestone_id
+            "milestone_id" => milestone_id,
+            "account_id" => caller
+        });
+
+        if finalized {
+            emit_event!("dispute_finalized", {
+                "contract_id" => contract_id,
+                "milestone_id" => milestone_id
+            });
+        }
+    }
+
+    /// Applies the contract's fallback policy once a pending dispute has waited past
+    /// its resolution deadline, capped at the remedy the client claimed. No AI fee
+    /// is charged on this path. An escalated dispute the fallback arbitrator has not
+    /// ruled on by its own deadline is split as a last resort.
+    pub fn trigger_fallback(&mut self, contract_id: String, milestone_id: String) {
+        let caller = env::predecessor_account_id();
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        assert!(contract.is_party(&caller), "Only contract parties can trigger the fallback");
+
+        let dispute_idx = contract
+            .find_open_dispute(&milestone_id)
+            .expect("No active dispute for this milestone");
+
+        let timed_out = contract.disputes[dispute_idx]
+            .resolution_deadline_ns
+            .map(|d| env::block_timestamp() >= d)
+            .unwrap_or(false);
+        assert!(timed_out, "Resolution deadline has not passed");
+
+        let escalated = contract.disputes[dispute_idx].status == DisputeStatus::Escalated;
+        let policy = if escalated {
+            FallbackPolicy::Split
+        } else {
+            contract.fallback_policy.clone()
+        };
+        let outcome = match policy {
+            FallbackPolicy::RefundClient => Some(Resolution::Client),
+            FallbackPolicy::Split => Some(Resolution::Split { freelancer_pct: 50 }),
+            FallbackPolicy::Arbitrator => None,
+        }
+        .map(|r| contract.cap_to_claim(dispute_idx, r));
+        let dispute = &mut contract.disputes[dispute_idx];
+        dispute.fallback_applied = true;
+        match policy {
+            FallbackPolicy::RefundClient | FallbackPolicy::Split => {
+                dispute.resolution = outcome;
+                dispute.status = DisputeStatus::Finalized;
+            }
+            FallbackPolicy::Arbitrator => {
+                dispute.escalated_to = Some(
+                    self.fallback_arbitrator
+                        .clone()
+                        .expect("No fallback arbitrator appointed"),
+                );
+                dispute.status = DisputeStatus::Escalated;
+                dispute.resolution_deadline_ns =
+                    Some(env::block_timestamp() + ESCALATION_TIMEOUT_NS);
+            }
+        }
+
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("dispute_fallback", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "policy" => format!("{:?}", policy),
+            "last_resort" => escalated
+        });
+    }
+
+    /// Entry point for human arbitrators, external arbitrator contracts answering
+    /// after the fact, and fallback arbitrators on escalated disputes.
+    pub fn submit_arbitrator_resolution(
+        &mut self,
+        contract_id: String,
+        milestone_id: String,
+        resolution: Resolution,
+        explanation: String,
+    ) {
+        self.require_not_paused(PauseScope::AiResolutions);
+        let caller = env::predecessor_account_id();
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+
+        if let Resolution::Split { freelancer_pct } = &resolution {
+            assert!(*freelancer_pct <= 100, "Invalid split percentage");
+        }
+
+        let dispute_idx = contract
+            .find_open_dispute(&milestone_id)
+            .expect("No active dispute for this milestone");
+
+        let dispute = &contract.disputes[dispute_idx];
+        let assigned = if dispute.status == DisputeStatus::Escalated {
+            dispute.escalated_to.as_ref()
+        } else {
+            contract.arbitrator.account_id()
+        };
+        assert!(
+            assigned == Some(&caller),
+            "Only the assigned arbitrator can resolve this dispute"
+        );
+        assert!(
+            dispute.response_window_closed_at(env::block_timestamp()),
+            "Dispute is still in its response window"
+        );
+
+        record_resolution(&mut contract, dispute_idx, resolution, explanation);
+
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("arbitrator_resolution", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "arbitrator" => caller
+        });
+    }
+
+    pub fn propose_settlement(
+        &mut self,
+        contract_id: String,
+        milestone_id: String,
+        resolution: Resolution,
+    ) {
+        let caller = env::predecessor_account_id();
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        assert!(contract.is_party(&caller), "Only contract parties can propose a settlement");
+
+        if let Resolution::Split { freelancer_pct } = &resolution {
+            assert!(*freelancer_pct <= 100, "Invalid split percentage");
+        }
+
+        let dispute_idx = contract
+            .find_dispute(&milestone_id, DisputeStatus::Pending)
+            .expect("No active dispute for this milestone");
+
+        contract.disputes[dispute_idx].settlement = Some(SettlementProposal {
+            proposed_by: caller.clone(),
+            resolution,
+            proposed_at: env::block_timestamp(),
+        });
+
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("settlement_proposed", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "proposed_by" => caller
+        });
+    }
+
+    pub fn get_operator_earnings(&self, account_id: AccountId) -> NearToken {
+        self.operator_earnings
+            .get(&account_id)
+            .copied()
+            .unwrap_or(NearToken::from_yoctonear(0))
+    }
+
+    /// Pays out the AI fee held for a dispute whose funds are being released, split
+    /// between the TEE operator who submitted the resolution and the protocol
+    /// treasury. Until then the fee stays in the contract, so a resolution signed by
+    /// a compromised key can be reset without clawing anything back.
+    pub(crate) fn pay_ai_fee(&mut self, dispute: &mut Dispute) {
+        let Some(operator) = dispute.ai_fee_operator.clone() else {
+            return;
+        };
+        if !dispute.ai_fee_deducted {
+            return;
+        }
+        let fee = dispute.ai_fee_amount;
+        let operator_share =
+            fee.as_yoctonear() * self.ai_fee_operator_share_bps as u128 / 10_000;
+        let treasury_share = fee.as_yoctonear() - operator_share;
+        dispute.ai_fee_operator_share = NearToken::from_yoctonear(operator_share);
+
+        self.treasury_balance =
+            NearToken::from_yoctonear(self.treasury_balance.as_yoctonear() + treasury_share);
+        if operator_share > 0 {
+            let earned = self.get_operator_earnings(operator.clone()).as_yoctonear();
+            self.operator_earnings
+                .insert(operator.clone(), NearToken::from_yoctonear(earned + operator_share));
+            let _ = Promise::new(operator.clone()).transfer(NearToken::from_yoctonear(operator_share));
+        }
+
+        emit_event!("ai_fee_paid", {
+            "operator" => operator,
+            "operator_share" => operator_share,
+            "treasury_share" => treasury_share
+        });
+    }
+
+    /// Accepting the counterparty's proposal closes the dispute without the AI,
+    /// so the AI fee is never deducted from the security pool. `expected` must match
+    /// the current proposal, so a replacement proposed meanwhile is never accepted.
+    pub fn accept_settlement(
+        &mut self,
+        contract_id: String,
+        milestone_id: String,
+        expected: Resolution,
+    ) {
+        let caller = env::predecessor_account_id();
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        assert!(contract.is_party(&caller), "Only contract parties can accept a settlement");
+
+        let dispute_idx = contract
+            .find_dispute(&milestone_id, DisputeStatus::Pending)
+            .expect("No active dispute for this milestone");
+
+        let proposal = contract.disputes[dispute_idx]
+            .settlement
+            .clone()
+            .expect("No settlement proposed");
+        assert!(proposal.proposed_by != caller, "Cannot accept your own settlement proposal");
+        assert!(proposal.resolution == expected, "Settlement proposal has changed");
+        if !proposal.resolution.is_refund_only() {
+            self.require_not_paused(PauseScope::Releases);
+        }
+
+        let dispute = &mut contract.disputes[dispute_idx];
+        dispute.status = DisputeStatus::Settled;
+        dispute.resolution = Some(proposal.resolution.clone());
+        dispute.funds_released = true;
+
+        let milestone_idx = contract.find_milestone(&milestone_id).expect("Milestone not found");
+        let (freelancer_amount, client_amount) =
+            contract.apply_resolution(milestone_idx, &proposal.resolution);
+
+        let platform_fee = self.disburse(&contract, freelancer_amount, client_amount);
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("dispute_settled", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "platform_fee" => platform_fee.as_yoctonear()
         });
     }
 
@@ -178,6 +639,9 @@ impl Contract {
         });
     }
 
+    /// Turns a Client or Split ruling into another rework round. Each milestone allows
+    /// a limited number of overrides, each followed by a longer payment cooldown;
+    /// overriding a split the freelancer accepted compensates them from the pool.
     pub fn override_to_continue_work(&mut self, contract_id: String, milestone_id: String) {
         let caller = env::predecessor_account_id();
         let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
@@ -186,13 +650,7 @@ impl Contract {
 
         let dispute_idx = contract
             .find_dispute(&milestone_id, DisputeStatus::AiResolved)
-            .or_else(|| {
-                contract.disputes.iter().position(|d| {
-                    d.milestone_id == milestone_id
-                        && d.status == DisputeStatus::Finalized
-                        && !d.funds_released
-                })
-            })
+            .or_else(|| contract.find_unreleased_dispute(&milestone_id))
             .expect("No overridable dispute for this milestone");
 
         let resolution = contract.disputes[dispute_idx]
@@ -204,22 +662,65 @@ impl Contract {
             matches!(resolution, Resolution::Client | Resolution::Split { .. }),
             "Override only allowed for Client or Split resolutions"
         );
+        assert!(
+            !contract.disputes[dispute_idx].client_waived(),
+            "Client waived the right to override by accepting the resolution"
+        );
+
+        let milestone_idx = contract.find_milestone(&milestone_id).expect("Milestone not found");
+        let overrides_used = contract.milestones[milestone_idx].overrides_used;
+        assert!(
+            overrides_used < MAX_OVERRIDES_PER_MILESTONE,
+            "Override limit reached; the resolution stands"
+        );
+
+        let freelancer_accepted = contract.disputes[dispute_idx].freelancer_accepted;
+        let compensation = match resolution {
+            Resolution::Split { freelancer_pct } if freelancer_accepted => {
+                let share = contract.milestones[milestone_idx].amount.as_yoctonear()
+                    * *freelancer_pct as u128
+                    / 100;
+                (share * OVERRIDE_COMPENSATION_PCT / 100).min(contract.security_pool.as_yoctonear())
+            }
+            _ => 0,
+        };
+        if compensation > 0 {
+            self.require_not_paused(PauseScope::Releases);
+            contract.security_pool =
+                NearToken::from_yoctonear(contract.security_pool.as_yoctonear() - compensation);
+        }
 
         contract.disputes[dispute_idx].status = DisputeStatus::Finalized;
         contract.disputes[dispute_idx].funds_released = true;
-
-        let milestone_idx = contract.find_milestone(&milestone_id).expect("Milestone not found");
-        contract.milestones[milestone_idx].status = MilestoneStatus::InProgress;
-        contract.milestones[milestone_idx].payment_request_deadline_ns = None;
-        contract.milestones[milestone_idx].payment_request_blocked_until_ns =
-            Some(env::block_timestamp() + PAYMENT_COOLDOWN_NS);
+        self.pay_ai_fee(&mut contract.disputes[dispute_idx]);
+
+        // Each further override doubles the cooldown before the next payment request;
+        // escrows created before the cooldown had a lower bound start from the default.
+        let base_cooldown_ns = match contract.time_windows.payment_cooldown_ns {
+            0 => PAYMENT_COOLDOWN_NS,
+            configured => configured,
+        };
+        let cooldown_ns = base_cooldown_ns << overrides_used;
+        let milestone = &mut contract.milestones[milestone_idx];
+        milestone.overrides_used += 1;
+        milestone.status = MilestoneStatus::InProgress;
+        milestone.payment_request_deadline_ns = None;
+        milestone.payment_request_blocked_until_ns = Some(env::block_timestamp() + cooldown_ns);
+        let overrides_left = MAX_OVERRIDES_PER_MILESTONE - milestone.overrides_used;
         contract.status = ContractStatus::Active;
 
+        let freelancer = contract.require_freelancer();
         self.contracts.insert(contract_id.clone(), contract);
 
+        if compensation > 0 {
+            let _ = Promise::new(freelancer).transfer(NearToken::from_yoctonear(compensation));
+        }
+
         emit_event!("dispute_override_continue", {
             "contract_id" => contract_id,
-            "milestone_id" => milestone_id
+            "milestone_id" => milestone_id,
+            "compensation" => compensation,
+            "overrides_left" => overrides_left
         });
     }
 }
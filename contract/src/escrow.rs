@@ -3,14 +3,18 @@ use near_sdk::{env, near_bindgen, NearToken, Promise};
 use crate::types::*;
 use crate::{Contract, ContractExt};
 
+pub(crate) const MAX_PLATFORM_FEE_BPS: u16 = 1_000;
+
 #[near_bindgen]
 impl Contract {
     #[payable]
     pub fn fund_contract(&mut self, contract_id: String) {
+        self.require_not_paused(PauseScope::Funding);
         let deposit = env::attached_deposit();
         assert!(deposit > NearToken::from_yoctonear(0), "Must attach NEAR to fund");
 
         let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        contract.require_not_on_hold();
         assert!(contract.status == ContractStatus::Active, "Contract must be active");
 
         let pct = contract.security_deposit_pct as u128;
@@ -60,10 +64,12 @@ impl Contract {
 
     #[payable]
     pub fn top_up_security(&mut self, contract_id: String) {
+        self.require_not_paused(PauseScope::Funding);
         let deposit = env::attached_deposit();
         assert!(deposit > NearToken::from_yoctonear(0), "Must attach NEAR");
 
         let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        contract.require_not_on_hold();
         let caller = env::predecessor_account_id();
         assert!(contract.is_party(&caller), "Only contract parties can top up security");
 
@@ -80,10 +86,11 @@ impl Contract {
     }
 
     pub fn approve_milestone(&mut self, contract_id: String, milestone_id: String) {
+        self.require_not_paused(PauseScope::Releases);
         let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        contract.require_not_on_hold();
         assert_eq!(contract.client, env::predecessor_account_id(), "Only client can approve");
 
-        let freelancer = contract.require_freelancer();
         let idx = contract.find_milestone(&milestone_id).expect("Milestone not found");
 
         assert!(
@@ -99,13 +106,14 @@ impl Contract {
             contract.status = ContractStatus::Completed;
         }
 
+        let platform_fee = self.disburse(&contract, amount, NearToken::from_yoctonear(0));
         self.contracts.insert(contract_id.clone(), contract);
-        let _ = Promise::new(freelancer).transfer(amount);
 
         emit_event!("milestone_approved", {
             "contract_id" => contract_id,
             "milestone_id" => milestone_id,
-            "amount" => amount.as_yoctonear()
+            "amount" => amount.as_yoctonear(),
+            "platform_fee" => platform_fee.as_yoctonear()
         });
     }
 
@@ -113,71 +121,64 @@ impl Contract {
         let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
 
         let dispute_idx = contract
-            .find_dispute(&milestone_id, DisputeStatus::Finalized)
-            .expect("No finalized dispute for this milestone");
+            .find_unreleased_dispute(&milestone_id)
+            .expect("No finalized dispute with unreleased funds for this milestone");
 
-        assert!(!contract.disputes[dispute_idx].funds_released, "Funds already released");
+        assert!(
+            !self.tee_revocations.iter().any(|r| r.covers(&contract.disputes[dispute_idx])),
+            "Resolution was signed by a revoked key and awaits re-arbitration"
+        );
         contract.disputes[dispute_idx].funds_released = true;
+        self.pay_ai_fee(&mut contract.disputes[dispute_idx]);
 
         let resolution = contract.disputes[dispute_idx]
             .resolution
             .clone()
             .expect("No resolution set");
+        if !resolution.is_refund_only() {
+            self.require_not_paused(PauseScope::Releases);
+        }
 
         let milestone_idx = contract.find_milestone(&milestone_id).expect("Milestone not found");
-        let amount = contract.milestones[milestone_idx].amount;
-        let freelancer = contract.require_freelancer();
-        let client = contract.client.clone();
-
-        match resolution {
-            Resolution::Freelancer => {
-                contract.milestones[milestone_idx].status = MilestoneStatus::Completed;
-                if contract.all_milestones_completed() {
-                    contract.status = ContractStatus::Resolved;
-                }
-                self.contracts.insert(contract_id.clone(), contract);
-                let _ = Promise::new(freelancer).transfer(amount);
-            }
-            Resolution::Client => {
-                contract.milestones[milestone_idx].status = MilestoneStatus::Completed;
-                if contract.all_milestones_completed() {
-                    contract.status = ContractStatus::Resolved;
-                }
-                self.contracts.insert(contract_id.clone(), contract);
-                let _ = Promise::new(client).transfer(amount);
-            }
-            Resolution::ContinueWork => {
-                contract.milestones[milestone_idx].status = MilestoneStatus::InProgress;
-                contract.milestones[milestone_idx].payment_request_deadline_ns = None;
-                contract.status = ContractStatus::Active;
-                self.contracts.insert(contract_id.clone(), contract);
-            }
-            Resolution::Split { freelancer_pct } => {
-                assert!(freelancer_pct <= 100, "Invalid split percentage");
-                contract.milestones[milestone_idx].status = MilestoneStatus::Completed;
-                let freelancer_amount = amount.as_yoctonear() * freelancer_pct as u128 / 100;
-                let client_amount = amount.as_yoctonear() - freelancer_amount;
-                if contract.all_milestones_completed() {
-                    contract.status = ContractStatus::Resolved;
-                }
-                self.contracts.insert(contract_id.clone(), contract);
-                if freelancer_amount > 0 {
-                    let _ = Promise::new(freelancer).transfer(NearToken::from_yoctonear(freelancer_amount));
-                }
-                if client_amount > 0 {
-                    let _ = Promise::new(client).transfer(NearToken::from_yoctonear(client_amount));
-                }
-            }
-        }
+        let (freelancer_amount, client_amount) = contract.apply_resolution(milestone_idx, &resolution);
+
+        let platform_fee = self.disburse(&contract, freelancer_amount, client_amount);
+        self.contracts.insert(contract_id.clone(), contract);
 
         emit_event!("dispute_funds_released", {
             "contract_id" => contract_id,
-            "milestone_id" => milestone_id
+            "milestone_id" => milestone_id,
+            "platform_fee" => platform_fee.as_yoctonear()
         });
     }
 
+    /// Pays out a milestone. The escrow's platform fee is taken from the freelancer's
+    /// share only and accrues to the treasury balance; the fee is returned.
+    pub(crate) fn disburse(
+        &mut self,
+        contract: &EscrowContract,
+        freelancer_amount: NearToken,
+        client_amount: NearToken,
+    ) -> NearToken {
+        let platform_fee =
+            freelancer_amount.as_yoctonear() * contract.platform_fee_bps as u128 / 10_000;
+        self.treasury_balance =
+            NearToken::from_yoctonear(self.treasury_balance.as_yoctonear() + platform_fee);
+
+        let freelancer_net = freelancer_amount.as_yoctonear() - platform_fee;
+        if freelancer_net > 0 {
+            let _ = Promise::new(contract.require_freelancer())
+                .transfer(NearToken::from_yoctonear(freelancer_net));
+        }
+        if client_amount.as_yoctonear() > 0 {
+            let _ = Promise::new(contract.client.clone()).transfer(client_amount);
+        }
+        NearToken::from_yoctonear(platform_fee)
+    }
+
     pub fn complete_contract_security(&mut self, contract_id: String) {
         let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        contract.require_not_on_hold();
 
         assert!(contract.all_milestones_completed(), "Not all milestones completed");
 
@@ -185,14 +186,28 @@ impl Contract {
         assert!(pool.as_yoctonear() > 0, "No security deposit to release");
 
         contract.security_pool = NearToken::from_yoctonear(0);
-        let freelancer = contract.require_freelancer();
+        // A freelancer who abandoned a milestone does not collect the deposit.
+        let recipient = if contract
+            .milestones
+            .iter()
+            .any(|m| m.status == MilestoneStatus::Refunded)
+        {
+            contract.client.clone()
+        } else {
+            contract.require_freelancer()
+        };
+        // Returning the deposit to the client stays open while releases are paused.
+        if recipient != contract.client {
+            self.require_not_paused(PauseScope::Releases);
+        }
 
         self.contracts.insert(contract_id.clone(), contract);
-        let _ = Promise::new(freelancer).transfer(pool);
+        let _ = Promise::new(recipient.clone()).transfer(pool);
 
         emit_event!("security_pool_released", {
             "contract_id" => contract_id,
-            "amount" => pool.as_yoctonear()
+            "amount" => pool.as_yoctonear(),
+            "recipient" => recipient
         });
     }
 }
@@ -0,0 +1,97 @@
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen};
+
+use crate::types::*;
+use crate::{Contract, ContractExt};
+
+pub(crate) const EVIDENCE_WINDOW_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+const MAX_EVIDENCE_PER_DISPUTE: usize = 32;
+const MAX_LOCATOR_LEN: usize = 256;
+
+/// Merkle root over the dispute's evidence in submission order. Leaves are the
+/// sha256 of each borsh-encoded commitment; an odd node is paired with itself.
+pub fn evidence_root(evidence: &[EvidenceCommitment]) -> Option<Vec<u8>> {
+    if evidence.is_empty() {
+        return None;
+    }
+    let mut level: Vec<Vec<u8>> = evidence
+        .iter()
+        .map(|e| env::sha256(&borsh::to_vec(e).expect("Failed to encode evidence")))
+        .collect();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let right = pair.get(1).unwrap_or(&pair[0]);
+                env::sha256(&[pair[0].as_slice(), right.as_slice()].concat())
+            })
+            .collect();
+    }
+    level.pop()
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Records a commitment to a piece of evidence held off-chain (NOVA, IPFS).
+    /// Either party may submit while the dispute is pending and both the evidence and
+    /// response windows are open, so the set is fixed before any agent can claim it.
+    pub fn submit_evidence(
+        &mut self,
+        contract_id: String,
+        milestone_id: String,
+        content_hash: Vec<u8>,
+        locator: String,
+    ) {
+        let caller = env::predecessor_account_id();
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        assert!(contract.is_party(&caller), "Only contract parties can submit evidence");
+        assert_eq!(content_hash.len(), 32, "Content hash must be a 32-byte sha256 hash");
+        assert!(
+            !locator.is_empty() && locator.len() <= MAX_LOCATOR_LEN,
+            "Evidence locator must be 1-256 bytes"
+        );
+
+        let dispute_idx = contract
+            .find_dispute(&milestone_id, DisputeStatus::Pending)
+            .expect("No active dispute for this milestone");
+        let dispute = &mut contract.disputes[dispute_idx];
+        let now = env::block_timestamp();
+        assert!(
+            dispute.evidence_deadline_ns.is_some_and(|d| now < d)
+                && !dispute.response_window_closed_at(now),
+            "Evidence submission window has closed"
+        );
+        assert!(
+            dispute.evidence.len() < MAX_EVIDENCE_PER_DISPUTE,
+            "Too many evidence submissions for this dispute"
+        );
+
+        dispute.evidence.push(EvidenceCommitment {
+            submitted_by: caller.clone(),
+            content_hash,
+            locator,
+            submitted_at_ns: now,
+        });
+        let index = dispute.evidence.len() - 1;
+
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("evidence_submitted", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "account_id" => caller,
+            "index" => index
+        });
+    }
+
+    /// Hex-encoded Merkle root of the dispute's evidence, if any exists.
+    pub fn get_evidence_root(&self, contract_id: String, milestone_id: String) -> Option<String> {
+        let contract = self.contracts.get(&contract_id)?;
+        let dispute = contract.latest_dispute(&milestone_id)?;
+        evidence_root(&dispute.evidence).map(|root| to_hex(&root))
+    }
+}
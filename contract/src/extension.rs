@@ -0,0 +1,131 @@
+use near_sdk::json_types::U64;
+use near_sdk::{env, near_bindgen};
+
+use crate::types::*;
+use crate::{Contract, ContractExt};
+
+const MAX_EXTENSION_NS: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+/// The deadline an extension targets, if it is currently set.
+fn deadline_mut<'a>(
+    contract: &'a mut EscrowContract,
+    milestone_idx: usize,
+    kind: &DeadlineKind,
+) -> Option<&'a mut u64> {
+    match kind {
+        DeadlineKind::DueDate => contract.milestones[milestone_idx].due_date_ns.as_mut(),
+        DeadlineKind::PaymentReview => {
+            let milestone = &mut contract.milestones[milestone_idx];
+            if milestone.status != MilestoneStatus::SubmittedForReview {
+                return None;
+            }
+            milestone.payment_request_deadline_ns.as_mut()
+        }
+        DeadlineKind::DisputeReview => {
+            let milestone_id = contract.milestones[milestone_idx].id.clone();
+            let dispute_idx = contract.find_dispute(&milestone_id, DisputeStatus::AiResolved)?;
+            contract.disputes[dispute_idx].deadline_ns.as_mut()
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Asks the counterparty to push one of the milestone's deadlines back by
+    /// `duration_ns`. Nothing changes until they accept; one request may be open
+    /// per milestone.
+    pub fn request_extension(
+        &mut self,
+        contract_id: String,
+        milestone_id: String,
+        deadline: DeadlineKind,
+        duration_ns: U64,
+    ) {
+        let caller = env::predecessor_account_id();
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        contract.require_not_on_hold();
+        assert!(contract.is_party(&caller), "Only contract parties can request extensions");
+        assert!(
+            duration_ns.0 > 0 && duration_ns.0 <= MAX_EXTENSION_NS,
+            "Extension duration out of range"
+        );
+
+        let idx = contract.find_milestone(&milestone_id).expect("Milestone not found");
+        assert!(
+            !contract.milestones[idx]
+                .extensions
+                .iter()
+                .any(|e| e.status == ExtensionStatus::Pending),
+            "An extension request is already pending"
+        );
+        let current = *deadline_mut(&mut contract, idx, &deadline).expect("Deadline is not set");
+        assert!(env::block_timestamp() < current, "Deadline has already passed");
+
+        contract.milestones[idx].extensions.push(DeadlineExtension {
+            deadline: deadline.clone(),
+            requested_by: caller.clone(),
+            duration_ns: duration_ns.0,
+            requested_at_ns: env::block_timestamp(),
+            status: ExtensionStatus::Pending,
+        });
+
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("extension_requested", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "deadline" => format!("{:?}", deadline),
+            "account_id" => caller,
+            "duration_ns" => duration_ns.0
+        });
+    }
+
+    /// The counterparty accepts or declines the pending extension request; the
+    /// requester may only decline, which withdraws it.
+    pub fn respond_to_extension(
+        &mut self,
+        contract_id: String,
+        milestone_id: String,
+        accept: bool,
+    ) {
+        let caller = env::predecessor_account_id();
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        contract.require_not_on_hold();
+        assert!(contract.is_party(&caller), "Only contract parties can respond to extensions");
+
+        let idx = contract.find_milestone(&milestone_id).expect("Milestone not found");
+        let ext_idx = contract.milestones[idx]
+            .extensions
+            .iter()
+            .position(|e| e.status == ExtensionStatus::Pending)
+            .expect("No pending extension request");
+        let extension = contract.milestones[idx].extensions[ext_idx].clone();
+        assert!(
+            !accept || extension.requested_by != caller,
+            "Only the counterparty can respond to an extension request"
+        );
+
+        let new_deadline = if accept {
+            let deadline = deadline_mut(&mut contract, idx, &extension.deadline)
+                .expect("Deadline is no longer set");
+            *deadline += extension.duration_ns;
+            Some(*deadline)
+        } else {
+            None
+        };
+        contract.milestones[idx].extensions[ext_idx].status = if accept {
+            ExtensionStatus::Accepted
+        } else {
+            ExtensionStatus::Rejected
+        };
+
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("extension_resolved", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "accepted" => accept,
+            "new_deadline_ns" => new_deadline.map(|d| d.to_string()).unwrap_or_default()
+        });
+    }
+}
@@ -0,0 +1,134 @@
+use near_sdk::json_types::U64;
+use near_sdk::{env, near_bindgen};
+
+use crate::types::*;
+use crate::{Contract, ContractExt};
+
+pub(crate) const DEFAULT_GOVERNANCE_DELAY_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+const MIN_GOVERNANCE_DELAY_NS: u64 = 60 * 60 * 1_000_000_000;
+const MAX_GOVERNANCE_DELAY_NS: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+#[near_bindgen]
+impl Contract {
+    /// Queues a change to the delay itself; the current delay still applies to it.
+    /// Bounded so the queue can neither be bypassed nor locked up for good.
+    pub fn set_governance_delay(&mut self, delay_ns: U64) -> u64 {
+        self.require_owner();
+        assert!(
+            (MIN_GOVERNANCE_DELAY_NS..=MAX_GOVERNANCE_DELAY_NS).contains(&delay_ns.0),
+            "Governance delay must be between 1 hour and 30 days"
+        );
+        self.queue_action(GovernanceAction::SetGovernanceDelay { delay_ns: delay_ns.0 })
+    }
+
+    pub fn get_governance_delay(&self) -> U64 {
+        U64(self.governance_delay_ns)
+    }
+
+    pub fn get_queued_actions(&self) -> &[QueuedAction] {
+        &self.queued_actions
+    }
+
+    /// Applies a queued action once its delay has passed.
+    pub fn execute_action(&mut self, action_id: u64) {
+        let idx = self.find_queued_action(action_id);
+        self.require_action_role(&self.queued_actions[idx].action);
+        assert!(
+            env::block_timestamp() >= self.queued_actions[idx].executable_at_ns,
+            "Action is still time-locked"
+        );
+
+        let queued = self.queued_actions.remove(idx);
+
+        match queued.action {
+            GovernanceAction::RegisterTeeKey {
+                address,
+                attestation_hash,
+                model_ids,
+                valid_from_ns,
+                valid_until_ns,
+                operator,
+            } => self.apply_register_tee_key(
+                address,
+                attestation_hash,
+                model_ids,
+                valid_from_ns,
+                valid_until_ns,
+                operator,
+            ),
+            GovernanceAction::RemoveTeeKey { address, compromised_at_ns } => {
+                self.apply_remove_tee_key(address, compromised_at_ns)
+            }
+            GovernanceAction::SetAiProcessingFee { fee } => self.ai_processing_fee = fee,
+            GovernanceAction::SetGovernanceDelay { delay_ns } => self.governance_delay_ns = delay_ns,
+            GovernanceAction::SetPlatformFee { fee_bps } => self.platform_fee_bps = fee_bps,
+            GovernanceAction::SetTreasury { account_id } => self.treasury = account_id,
+            GovernanceAction::SetOperatorFeeShare { share_bps } => {
+                self.ai_fee_operator_share_bps = share_bps
+            }
+            GovernanceAction::SetFallbackArbitrator { account_id } => {
+                self.fallback_arbitrator = account_id
+            }
+            GovernanceAction::SetAiResolutionTimeout { timeout_ns } => {
+                self.ai_resolution_timeout_ns = timeout_ns
+            }
+            GovernanceAction::SetTimeWindowBounds { min, max } => {
+                self.min_time_windows = min;
+                self.max_time_windows = max;
+            }
+            GovernanceAction::UpgradeContract { .. } => {
+                env::panic_str("Upgrades are applied with update_contract")
+            }
+        }
+
+        emit_event!("governance_action_executed", {
+            "action_id" => action_id
+        });
+    }
+
+    pub fn cancel_action(&mut self, action_id: u64) {
+        let idx = self.find_queued_action(action_id);
+        self.require_action_role(&self.queued_actions[idx].action);
+        self.queued_actions.remove(idx);
+
+        emit_event!("governance_action_cancelled", {
+            "action_id" => action_id
+        });
+    }
+
+    fn find_queued_action(&self, action_id: u64) -> usize {
+        self.queued_actions
+            .iter()
+            .position(|a| a.id == action_id)
+            .expect("Queued action not found")
+    }
+
+    fn require_action_role(&self, action: &GovernanceAction) {
+        match action.role() {
+            Some(role) => self.require_role(role),
+            None => self.require_owner(),
+        }
+    }
+
+    pub(crate) fn queue_action(&mut self, action: GovernanceAction) -> u64 {
+        let id = self.next_action_id;
+        self.next_action_id += 1;
+
+        let now = env::block_timestamp();
+        let executable_at_ns = now
+            .checked_add(self.governance_delay_ns)
+            .expect("Governance delay overflows the timestamp");
+        self.queued_actions.push(QueuedAction {
+            id,
+            action,
+            proposed_at_ns: now,
+            executable_at_ns,
+        });
+
+        emit_event!("governance_action_queued", {
+            "action_id" => id,
+            "executable_at_ns" => executable_at_ns
+        });
+        id
+    }
+}
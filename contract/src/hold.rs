@@ -0,0 +1,154 @@
+use near_sdk::{env, near_bindgen, NearToken, Promise};
+
+use crate::types::*;
+use crate::{Contract, ContractExt};
+
+#[near_bindgen]
+impl Contract {
+    /// Proposes putting an active contract on hold. Takes effect once the
+    /// counterparty calls `accept_hold`; open disputes must be closed first.
+    pub fn propose_hold(&mut self, contract_id: String) {
+        let caller = env::predecessor_account_id();
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        assert!(contract.is_party(&caller), "Only contract parties can propose a hold");
+        assert!(contract.status == ContractStatus::Active, "Contract must be active");
+        assert!(
+            !contract.milestones.iter().any(|m| m.status == MilestoneStatus::Disputed),
+            "Cannot hold a contract with an open dispute"
+        );
+        assert!(contract.hold.is_none(), "A hold is already proposed");
+
+        contract.hold = Some(ContractHold {
+            proposed_by: caller.clone(),
+            held_at_ns: None,
+            client_cancelled: false,
+            freelancer_cancelled: false,
+        });
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("hold_proposed", {
+            "contract_id" => contract_id,
+            "account_id" => caller
+        });
+    }
+
+    /// The counterparty agrees to the hold. Either party can drop a proposed hold
+    /// with `accept = false`.
+    pub fn accept_hold(&mut self, contract_id: String, accept: bool) {
+        let caller = env::predecessor_account_id();
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        assert!(contract.is_party(&caller), "Only contract parties can respond to a hold");
+
+        let hold = contract.hold.as_mut().expect("No hold proposed");
+        assert!(hold.held_at_ns.is_none(), "Contract is already on hold");
+        if !accept {
+            contract.hold = None;
+        } else {
+            assert!(hold.proposed_by != caller, "Only the counterparty can accept a hold");
+            assert!(
+                contract.status == ContractStatus::Active
+                    && !contract.milestones.iter().any(|m| m.status == MilestoneStatus::Disputed),
+                "Contract can no longer be put on hold"
+            );
+            hold.held_at_ns = Some(env::block_timestamp());
+            contract.status = ContractStatus::OnHold;
+        }
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("hold_resolved", {
+            "contract_id" => contract_id,
+            "account_id" => caller,
+            "accepted" => accept
+        });
+    }
+
+    /// Lifts the hold. Every outstanding deadline moves forward by the time spent
+    /// on hold, so no window runs out while the project was paused.
+    pub fn resume_contract(&mut self, contract_id: String) {
+        let caller = env::predecessor_account_id();
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        assert!(contract.is_party(&caller), "Only contract parties can resume");
+        assert!(contract.status == ContractStatus::OnHold, "Contract is not on hold");
+
+        let held_at = contract
+            .hold
+            .take()
+            .and_then(|h| h.held_at_ns)
+            .expect("Contract is not on hold");
+        let paused_ns = env::block_timestamp() - held_at;
+        for milestone in contract.milestones.iter_mut() {
+            for deadline in [
+                &mut milestone.payment_request_deadline_ns,
+                &mut milestone.payment_request_blocked_until_ns,
+                &mut milestone.due_date_ns,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                *deadline += paused_ns;
+            }
+        }
+        contract.restore_status();
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("contract_resumed", {
+            "contract_id" => contract_id,
+            "account_id" => caller,
+            "paused_ns" => paused_ns
+        });
+    }
+
+    /// Records the caller's consent to cancel a held contract. Once both parties
+    /// agree, every unreleased milestone and the security pool go back to the client.
+    pub fn cancel_held_contract(&mut self, contract_id: String) {
+        let caller = env::predecessor_account_id();
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        assert!(contract.is_party(&caller), "Only contract parties can cancel");
+        assert!(contract.status == ContractStatus::OnHold, "Contract is not on hold");
+
+        let is_client = caller == contract.client;
+        let hold = contract.hold.as_mut().expect("Contract is not on hold");
+        if is_client {
+            hold.client_cancelled = true;
+        } else {
+            hold.freelancer_cancelled = true;
+        }
+        if !(hold.client_cancelled && hold.freelancer_cancelled) {
+            self.contracts.insert(contract_id.clone(), contract);
+            emit_event!("cancellation_proposed", {
+                "contract_id" => contract_id,
+                "account_id" => caller
+            });
+            return;
+        }
+
+        let released: u128 = contract
+            .milestones
+            .iter()
+            .filter(|m| matches!(m.status, MilestoneStatus::Completed | MilestoneStatus::Refunded))
+            .map(|m| m.amount.as_yoctonear())
+            .sum();
+        let refund = contract.funded_amount.as_yoctonear().saturating_sub(released)
+            + contract.security_pool.as_yoctonear();
+        for milestone in contract.milestones.iter_mut() {
+            if milestone.status != MilestoneStatus::Completed {
+                milestone.status = MilestoneStatus::Refunded;
+                milestone.payment_request_deadline_ns = None;
+            }
+        }
+        contract.security_pool = NearToken::from_yoctonear(0);
+        contract.status = ContractStatus::Cancelled;
+        contract.hold = None;
+        let client = contract.client.clone();
+        self.contracts.insert(contract_id.clone(), contract);
+
+        if refund > 0 {
+            let _ = Promise::new(client).transfer(NearToken::from_yoctonear(refund));
+        }
+
+        emit_event!("contract_cancelled", {
+            "contract_id" => contract_id,
+            "refund" => refund
+        });
+    }
+}
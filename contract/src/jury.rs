@@ -0,0 +1,285 @@
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, AccountId, NearToken, Promise};
+
+use crate::types::*;
+use crate::{Contract, ContractExt};
+
+const JURY_SIZE: usize = 3;
+const MIN_JUROR_STAKE: NearToken = NearToken::from_near(5);
+const APPEAL_FEE: NearToken = NearToken::from_near(1);
+const COMMIT_PERIOD_NS: u64 = 48 * 60 * 60 * 1_000_000_000;
+const REVEAL_PERIOD_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+const SLASH_PCT: u128 = 10;
+
+/// Commitment a juror submits in `commit_vote`: sha256 over the JSON-encoded
+/// resolution followed by the juror's secret salt.
+pub fn vote_commitment(resolution: &Resolution, salt: &str) -> Vec<u8> {
+    let encoded = near_sdk::serde_json::to_string(resolution).expect("Failed to encode resolution");
+    env::sha256(format!("{}{}", encoded, salt).as_bytes())
+}
+
+#[near_bindgen]
+impl Contract {
+    #[payable]
+    pub fn stake_as_juror(&mut self) {
+        let deposit = env::attached_deposit();
+        assert!(deposit > NearToken::from_yoctonear(0), "Must attach NEAR to stake");
+
+        let account = env::predecessor_account_id();
+        let mut juror = self.jurors.get(&account).cloned().unwrap_or(Juror {
+            stake: NearToken::from_yoctonear(0),
+            active_cases: 0,
+        });
+        juror.stake = NearToken::from_yoctonear(juror.stake.as_yoctonear() + deposit.as_yoctonear());
+        assert!(juror.stake >= MIN_JUROR_STAKE, "Stake below the juror minimum");
+        self.jurors.insert(account.clone(), juror);
+
+        emit_event!("juror_staked", {
+            "account_id" => account,
+            "amount" => deposit.as_yoctonear()
+        });
+    }
+
+    pub fn unstake_juror(&mut self, amount: U128) {
+        let account = env::predecessor_account_id();
+        let mut juror = self.jurors.get(&account).cloned().expect("Not a juror");
+        assert_eq!(juror.active_cases, 0, "Cannot unstake while serving on a jury");
+        assert!(amount.0 <= juror.stake.as_yoctonear(), "Amount exceeds stake");
+
+        juror.stake = NearToken::from_yoctonear(juror.stake.as_yoctonear() - amount.0);
+        if juror.stake.is_zero() {
+            self.jurors.remove(&account);
+        } else {
+            self.jurors.insert(account.clone(), juror);
+        }
+        let _ = Promise::new(account.clone()).transfer(NearToken::from_yoctonear(amount.0));
+
+        emit_event!("juror_unstaked", {
+            "account_id" => account,
+            "amount" => amount.0
+        });
+    }
+
+    pub fn get_juror(&self, account_id: AccountId) -> Option<Juror> {
+        self.jurors.get(&account_id).cloned()
+    }
+
+    /// Escalates an AI resolution to a juror panel. Only a losing party who has
+    /// not accepted the resolution may appeal, and only during the review window.
+    #[payable]
+    pub fn appeal_to_jury(&mut self, contract_id: String, milestone_id: String) {
+        let caller = env::predecessor_account_id();
+        let appeal_fee = env::attached_deposit();
+        assert!(appeal_fee >= APPEAL_FEE, "Attach the appeal fee");
+
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        assert!(contract.is_party(&caller), "Only contract parties can appeal");
+
+        let dispute_idx = contract
+            .find_dispute(&milestone_id, DisputeStatus::AiResolved)
+            .expect("No resolved dispute to appeal");
+        let dispute = &contract.disputes[dispute_idx];
+
+        let in_window = dispute
+            .deadline_ns
+            .map(|d| env::block_timestamp() < d)
+            .unwrap_or(false);
+        assert!(in_window, "Appeal window has closed");
+
+        let resolution = dispute.resolution.as_ref().expect("No resolution set");
+        let (loses, waived) = if caller == contract.client {
+            (resolution.client_loses(), dispute.client_waived())
+        } else {
+            (resolution.freelancer_loses(), dispute.freelancer_waived())
+        };
+        assert!(loses, "Only the losing party can appeal");
+        assert!(!waived, "Appeal right waived by accepting the resolution");
+
+        let panel = self.draw_jurors(&contract);
+        for juror in &panel {
+            let mut record = self.jurors.get(juror).cloned().expect("Juror not found");
+            record.active_cases += 1;
+            self.jurors.insert(juror.clone(), record);
+        }
+
+        let now = env::block_timestamp();
+        let dispute = &mut contract.disputes[dispute_idx];
+        dispute.status = DisputeStatus::Appealed;
+        dispute.jury = Some(JuryPanel {
+            appellant: caller.clone(),
+            appeal_fee,
+            votes: panel
+                .into_iter()
+                .map(|juror| JurorVote { juror, commitment: None, vote: None })
+                .collect(),
+            commit_deadline_ns: now + COMMIT_PERIOD_NS,
+            reveal_deadline_ns: now + COMMIT_PERIOD_NS + REVEAL_PERIOD_NS,
+        });
+
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("dispute_appealed", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "appellant" => caller
+        });
+    }
+
+    pub fn commit_vote(&mut self, contract_id: String, milestone_id: String, commitment: Vec<u8>) {
+        let caller = env::predecessor_account_id();
+        assert_eq!(commitment.len(), 32, "Commitment must be a 32-byte sha256 hash");
+
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        let dispute_idx = contract
+            .find_dispute(&milestone_id, DisputeStatus::Appealed)
+            .expect("No appealed dispute for this milestone");
+        let jury = contract.disputes[dispute_idx].jury.as_mut().expect("No jury drawn");
+
+        assert!(env::block_timestamp() < jury.commit_deadline_ns, "Commit period has ended");
+        let vote = jury
+            .votes
+            .iter_mut()
+            .find(|v| v.juror == caller)
+            .expect("Not a juror on this panel");
+        vote.commitment = Some(commitment);
+
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("jury_vote_committed", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "juror" => caller
+        });
+    }
+
+    pub fn reveal_vote(
+        &mut self,
+        contract_id: String,
+        milestone_id: String,
+        resolution: Resolution,
+        salt: String,
+    ) {
+        let caller = env::predecessor_account_id();
+        if let Resolution::Split { freelancer_pct } = &resolution {
+            assert!(*freelancer_pct <= 100, "Invalid split percentage");
+        }
+
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        let dispute_idx = contract
+            .find_dispute(&milestone_id, DisputeStatus::Appealed)
+            .expect("No appealed dispute for this milestone");
+        let jury = contract.disputes[dispute_idx].jury.as_mut().expect("No jury drawn");
+
+        let now = env::block_timestamp();
+        assert!(now >= jury.commit_deadline_ns, "Commit period has not ended");
+        assert!(now < jury.reveal_deadline_ns, "Reveal period has ended");
+
+        let vote = jury
+            .votes
+            .iter_mut()
+            .find(|v| v.juror == caller)
+            .expect("Not a juror on this panel");
+        assert!(vote.vote.is_none(), "Vote already revealed");
+        assert!(
+            vote.commitment.as_deref() == Some(vote_commitment(&resolution, &salt).as_slice()),
+            "Vote does not match commitment"
+        );
+        vote.vote = Some(resolution);
+
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("jury_vote_revealed", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "juror" => caller
+        });
+    }
+
+    /// Closes the jury once the reveal period ends. A strict majority of revealed
+    /// votes replaces the AI resolution; majority jurors share the appeal fee and
+    /// the stake slashed from everyone else. Without a majority the AI resolution
+    /// stands and the appeal fee is refunded. Either way the dispute is finalized
+    /// and paid out through `release_dispute_funds`.
+    pub fn tally_jury(&mut self, contract_id: String, milestone_id: String) {
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        let dispute_idx = contract
+            .find_dispute(&milestone_id, DisputeStatus::Appealed)
+            .expect("No appealed dispute for this milestone");
+        let jury = contract.disputes[dispute_idx].jury.clone().expect("No jury drawn");
+        assert!(
+            env::block_timestamp() >= jury.reveal_deadline_ns,
+            "Reveal period has not ended"
+        );
+
+        let revealed: Vec<&Resolution> = jury.votes.iter().filter_map(|v| v.vote.as_ref()).collect();
+        let majority = revealed
+            .iter()
+            .find(|candidate| revealed.iter().filter(|r| r == candidate).count() * 2 > revealed.len())
+            .map(|r| (*r).clone());
+
+        for vote in &jury.votes {
+            let mut juror = self.jurors.get(&vote.juror).cloned().expect("Juror not found");
+            juror.active_cases -= 1;
+            self.jurors.insert(vote.juror.clone(), juror);
+        }
+
+        match &majority {
+            Some(decision) => {
+                let (winners, losers): (Vec<&JurorVote>, Vec<&JurorVote>) =
+                    jury.votes.iter().partition(|v| v.vote.as_ref() == Some(decision));
+
+                let mut reward_pool = jury.appeal_fee.as_yoctonear();
+                for vote in losers {
+                    let mut juror = self.jurors.get(&vote.juror).cloned().expect("Juror not found");
+                    let slashed = juror.stake.as_yoctonear() * SLASH_PCT / 100;
+                    juror.stake = NearToken::from_yoctonear(juror.stake.as_yoctonear() - slashed);
+                    reward_pool += slashed;
+                    self.jurors.insert(vote.juror.clone(), juror);
+                }
+
+                let share = reward_pool / winners.len() as u128;
+                for vote in winners {
+                    let mut juror = self.jurors.get(&vote.juror).cloned().expect("Juror not found");
+                    juror.stake = NearToken::from_yoctonear(juror.stake.as_yoctonear() + share);
+                    self.jurors.insert(vote.juror.clone(), juror);
+                }
+
+                let capped = contract.cap_to_claim(dispute_idx, decision.clone());
+                contract.disputes[dispute_idx].resolution = Some(capped);
+            }
+            None => {
+                let _ = Promise::new(jury.appellant.clone()).transfer(jury.appeal_fee);
+            }
+        }
+
+        contract.disputes[dispute_idx].status = DisputeStatus::Finalized;
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("jury_decided", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "majority_reached" => majority.is_some()
+        });
+    }
+
+    /// Draws the panel with a partial Fisher-Yates shuffle seeded by the block's
+    /// random seed. Parties to the escrow are never eligible.
+    fn draw_jurors(&self, contract: &EscrowContract) -> Vec<AccountId> {
+        let mut candidates: Vec<AccountId> = self
+            .jurors
+            .iter()
+            .filter(|(account, juror)| juror.stake >= MIN_JUROR_STAKE && !contract.is_party(account))
+            .map(|(account, _)| account.clone())
+            .collect();
+        assert!(candidates.len() >= JURY_SIZE, "Not enough jurors in the pool");
+
+        let seed = env::random_seed();
+        for i in 0..JURY_SIZE {
+            let rand = u16::from_le_bytes([seed[2 * i], seed[2 * i + 1]]) as usize;
+            let j = i + rand % (candidates.len() - i);
+            candidates.swap(i, j);
+        }
+        candidates.truncate(JURY_SIZE);
+        candidates
+    }
+}
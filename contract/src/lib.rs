@@ -1,7 +1,9 @@
+#![allow(clippy::too_many_arguments)]
+
 use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
 use near_sdk::store::IterableMap;
-use near_sdk::json_types::U128;
-use near_sdk::{env, near_bindgen, AccountId, NearToken, PanicOnDefault};
+use near_sdk::json_types::{U128, U64};
+use near_sdk::{env, near_bindgen, AccountId, NearToken, PanicOnDefault, Promise};
 
 macro_rules! emit_event {
     ($event:expr, { $($key:expr => $val:expr),* $(,)? }) => {
@@ -13,11 +15,29 @@ macro_rules! emit_event {
     };
 }
 
+mod arbitrator;
 mod dispute;
 mod escrow;
+mod evidence;
+mod extension;
+mod governance;
+mod hold;
+mod jury;
+mod migration;
 mod milestone;
+mod pause;
+mod roles;
+mod statement;
 pub mod types;
 
+use dispute::{
+    DEFAULT_AI_RESOLUTION_TIMEOUT_NS, DISPUTE_DEADLINE_NS, MAX_AI_PROCESSING_FEE, PAYMENT_COOLDOWN_NS,
+};
+use escrow::MAX_PLATFORM_FEE_BPS;
+use governance::DEFAULT_GOVERNANCE_DELAY_NS;
+use milestone::{
+    DEFAULT_REVISION_LIMIT, MAX_REVISION_LIMIT, OVERDUE_GRACE_NS, PAYMENT_REQUEST_DEADLINE_NS,
+};
 use types::*;
 
 #[near_bindgen]
@@ -26,23 +46,68 @@ use types::*;
 pub struct Contract {
     pub contracts: IterableMap<String, EscrowContract>,
     pub account_contracts: IterableMap<AccountId, Vec<String>>,
-    pub trusted_tee_addresses: Vec<Vec<u8>>,
+    pub tee_keys: Vec<TeeKey>,
     pub owner: AccountId,
     pub next_id: u64,
     pub ai_processing_fee: NearToken,
+    pub ai_resolution_timeout_ns: u64,
+    pub fallback_arbitrator: Option<AccountId>,
+    pub jurors: IterableMap<AccountId, Juror>,
+    pub pending_owner: Option<AccountId>,
+    pub governance_delay_ns: u64,
+    pub queued_actions: Vec<QueuedAction>,
+    pub next_action_id: u64,
+    pub roles: IterableMap<Role, Vec<AccountId>>,
+    pub paused_scopes: Vec<PauseScope>,
+    pub platform_fee_bps: u16,
+    pub treasury: AccountId,
+    pub treasury_balance: NearToken,
+    pub ai_fee_operator_share_bps: u16,
+    pub operator_earnings: IterableMap<AccountId, NearToken>,
+    pub min_time_windows: TimeWindows,
+    pub max_time_windows: TimeWindows,
+    pub legacy_contracts: Option<IterableMap<String, migration::EscrowContractV0>>,
+    pub tee_revocations: Vec<TeeRevocation>,
 }
 
 #[near_bindgen]
 impl Contract {
     #[init]
     pub fn new(owner: AccountId) -> Self {
+        migration::write_state_version();
         Self {
-            contracts: IterableMap::new(b"c"),
+            contracts: IterableMap::new(b"e"),
             account_contracts: IterableMap::new(b"a"),
-            trusted_tee_addresses: vec![],
+            tee_keys: vec![],
+            treasury: owner.clone(),
             owner,
             next_id: 0,
             ai_processing_fee: NearToken::from_yoctonear(0),
+            ai_resolution_timeout_ns: DEFAULT_AI_RESOLUTION_TIMEOUT_NS,
+            fallback_arbitrator: None,
+            jurors: IterableMap::new(b"j"),
+            pending_owner: None,
+            governance_delay_ns: DEFAULT_GOVERNANCE_DELAY_NS,
+            queued_actions: vec![],
+            next_action_id: 0,
+            roles: IterableMap::new(b"r"),
+            paused_scopes: vec![],
+            platform_fee_bps: 0,
+            treasury_balance: NearToken::from_yoctonear(0),
+            ai_fee_operator_share_bps: 10_000,
+            operator_earnings: IterableMap::new(b"o"),
+            min_time_windows: TimeWindows {
+                payment_review_ns: 12 * HOUR_NS,
+                dispute_review_ns: 12 * HOUR_NS,
+                payment_cooldown_ns: HOUR_NS,
+            },
+            max_time_windows: TimeWindows {
+                payment_review_ns: 14 * 24 * HOUR_NS,
+                dispute_review_ns: 14 * 24 * HOUR_NS,
+                payment_cooldown_ns: 7 * 24 * HOUR_NS,
+            },
+            legacy_contracts: None,
+            tee_revocations: vec![],
         }
     }
 
@@ -54,14 +119,32 @@ impl Contract {
         );
     }
 
+    /// First step of an ownership transfer; `new_owner` must call `accept_ownership`.
     pub fn transfer_ownership(&mut self, new_owner: AccountId) {
         self.require_owner();
-        self.owner = new_owner.clone();
-        emit_event!("ownership_transferred", {
+        self.pending_owner = Some(new_owner.clone());
+        emit_event!("ownership_transfer_proposed", {
             "new_owner" => new_owner
         });
     }
 
+    pub fn accept_ownership(&mut self) {
+        let caller = env::predecessor_account_id();
+        assert!(
+            self.pending_owner.as_ref() == Some(&caller),
+            "Only the pending owner can accept ownership"
+        );
+        self.owner = caller.clone();
+        self.pending_owner = None;
+        emit_event!("ownership_transferred", {
+            "new_owner" => caller
+        });
+    }
+
+    pub fn get_pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner.clone()
+    }
+
     pub fn get_owner(&self) -> &AccountId {
         &self.owner
     }
@@ -72,29 +155,316 @@ impl Contract {
         self.account_contracts.insert(account.clone(), ids);
     }
 
-    pub fn register_tee_address(&mut self, address: Vec<u8>) {
+    /// Queues a TEE key registration; see `execute_action`.
+    pub fn register_tee_address(
+        &mut self,
+        address: Vec<u8>,
+        attestation_hash: String,
+        model_ids: Vec<String>,
+        valid_from_ns: Option<U64>,
+        valid_until_ns: Option<U64>,
+        operator: AccountId,
+    ) -> u64 {
+        self.require_role(Role::TeeRegistryManager);
+        assert_eq!(address.len(), 32, "Signing address must be 32 bytes");
+        assert!(!attestation_hash.is_empty(), "Attestation report hash required");
+        assert!(!model_ids.is_empty(), "At least one model id required");
+        if let (Some(from), Some(until)) = (valid_from_ns, valid_until_ns) {
+            assert!(until.0 > from.0, "Validity window must end after it starts");
+        }
+
+        self.queue_action(GovernanceAction::RegisterTeeKey {
+            address,
+            attestation_hash,
+            model_ids,
+            valid_from_ns: valid_from_ns.map(|v| v.0),
+            valid_until_ns: valid_until_ns.map(|v| v.0),
+            operator,
+        })
+    }
+
+    fn apply_register_tee_key(
+        &mut self,
+        address: Vec<u8>,
+        attestation_hash: String,
+        model_ids: Vec<String>,
+        valid_from_ns: Option<u64>,
+        valid_until_ns: Option<u64>,
+        operator: AccountId,
+    ) {
+        let now = env::block_timestamp();
+        let valid_from_ns = valid_from_ns.unwrap_or(now);
+        if let Some(until) = valid_until_ns {
+            assert!(until > valid_from_ns, "Validity window must end after it starts");
+        }
+
+        let key = TeeKey {
+            public_key: address.clone(),
+            attestation_hash,
+            model_ids,
+            valid_from_ns,
+            valid_until_ns,
+            operator: operator.clone(),
+            registered_at_ns: now,
+        };
+        match self.tee_keys.iter().position(|k| k.public_key == address) {
+            Some(idx) => self.tee_keys[idx] = key,
+            None => self.tee_keys.push(key),
+        }
+
+        emit_event!("tee_key_registered", {
+            "operator" => operator
+        });
+    }
+
+    /// Queues a TEE key revocation; see `execute_action`.
+    pub fn remove_tee_address(&mut self, address: Vec<u8>, compromised_at_ns: Option<U64>) -> u64 {
+        self.require_role(Role::TeeRegistryManager);
+        self.queue_action(GovernanceAction::RemoveTeeKey {
+            address,
+            compromised_at_ns: compromised_at_ns.map(|t| t.0),
+        })
+    }
+
+    /// Revokes a TEE key. With `compromised_at_ns`, the resolutions that key signed
+    /// at or after that time are queued for re-arbitration; see
+    /// `invalidate_compromised_resolutions`.
+    fn apply_remove_tee_key(&mut self, address: Vec<u8>, compromised_at_ns: Option<u64>) {
+        self.tee_keys.retain(|k| k.public_key != address);
+
+        emit_event!("tee_key_removed", {
+            "retroactive" => compromised_at_ns.is_some()
+        });
+
+        if let Some(compromised_at_ns) = compromised_at_ns {
+            self.tee_revocations.retain(|r| r.address != address);
+            self.tee_revocations.push(TeeRevocation {
+                address,
+                compromised_at_ns,
+                next_index: 0,
+            });
+        }
+    }
+
+    /// Walks the next `limit` escrows for a revoked key and sends every unpaid
+    /// resolution it signed after the compromise, including appealed ones, back to
+    /// `Pending`. Their AI fees are still held by the contract and return to the
+    /// security pool. Anyone may advance the scan. Returns how many escrows are left.
+    pub fn invalidate_compromised_resolutions(&mut self, address: Vec<u8>, limit: u32) -> u32 {
+        let revocation_idx = self
+            .tee_revocations
+            .iter()
+            .position(|r| r.address == address)
+            .expect("No pending revocation for this key");
+        let revocation = self.tee_revocations[revocation_idx].clone();
+
+        let resolution_deadline_ns = env::block_timestamp() + self.ai_resolution_timeout_ns;
+        let contract_ids: Vec<String> = self
+            .contracts
+            .keys()
+            .skip(revocation.next_index as usize)
+            .take(limit as usize)
+            .cloned()
+            .collect();
+
+        for contract_id in contract_ids.iter() {
+            let mut contract = self.contracts.get(contract_id).cloned().expect("Contract not found");
+            let mut invalidated = vec![];
+
+            for dispute in contract.disputes.iter_mut() {
+                let unpaid = match dispute.status {
+                    DisputeStatus::AiResolved | DisputeStatus::Appealed => true,
+                    DisputeStatus::Finalized => !dispute.funds_released,
+                    _ => false,
+                };
+                if !unpaid || !revocation.covers(dispute) {
+                    continue;
+                }
+
+                if dispute.status == DisputeStatus::Appealed {
+                    let jury = dispute.jury.take().expect("No jury drawn");
+                    for vote in &jury.votes {
+                        let mut juror = self.jurors.get(&vote.juror).cloned().expect("Juror not found");
+                        juror.active_cases -= 1;
+                        self.jurors.insert(vote.juror.clone(), juror);
+                    }
+                    let _ = Promise::new(jury.appellant).transfer(jury.appeal_fee);
+                }
+                // Fees the original release paid out at resolution stay deducted, so
+                // re-arbitration does not charge the pool twice.
+                if dispute.ai_fee_deducted && dispute.ai_fee_operator.take().is_some() {
+                    let fee = dispute.ai_fee_amount.as_yoctonear();
+                    contract.security_pool =
+                        NearToken::from_yoctonear(contract.security_pool.as_yoctonear() + fee);
+                    dispute.ai_fee_deducted = false;
+                    dispute.ai_fee_amount = NearToken::from_yoctonear(0);
+                }
+                dispute.reset_for_rearbitration(resolution_deadline_ns);
+                invalidated.push(dispute.milestone_id.clone());
+            }
+
+            if invalidated.is_empty() {
+                continue;
+            }
+            self.contracts.insert(contract_id.clone(), contract);
+            for milestone_id in invalidated {
+                emit_event!("resolution_invalidated", {
+                    "contract_id" => contract_id,
+                    "milestone_id" => milestone_id
+                });
+            }
+        }
+
+        let next_index = revocation.next_index + contract_ids.len() as u32;
+        let remaining = self.contracts.len().saturating_sub(next_index);
+        if remaining == 0 {
+            self.tee_revocations.remove(revocation_idx);
+        } else {
+            self.tee_revocations[revocation_idx].next_index = next_index;
+        }
+        remaining
+    }
+
+    pub fn get_tee_revocations(&self) -> &[TeeRevocation] {
+        &self.tee_revocations
+    }
+
+    /// Keys that can sign resolutions right now.
+    pub fn get_trusted_tee_addresses(&self) -> Vec<Vec<u8>> {
+        let now = env::block_timestamp();
+        self.tee_keys
+            .iter()
+            .filter(|k| k.is_valid_at(now))
+            .map(|k| k.public_key.clone())
+            .collect()
+    }
+
+    pub fn get_tee_registry(&self) -> &[TeeKey] {
+        &self.tee_keys
+    }
+
+    pub(crate) fn find_tee_key(&self, address: &[u8]) -> Option<&TeeKey> {
+        self.tee_keys.iter().find(|k| k.public_key == address)
+    }
+
+    /// Queues an AI processing fee change; see `execute_action`. Existing escrows
+    /// keep the fee they were created with.
+    pub fn set_ai_processing_fee(&mut self, fee_yoctonear: U128) -> u64 {
+        self.require_role(Role::FeeManager);
+        assert!(
+            fee_yoctonear.0 <= MAX_AI_PROCESSING_FEE.as_yoctonear(),
+            "AI processing fee exceeds the protocol maximum"
+        );
+        self.queue_action(GovernanceAction::SetAiProcessingFee {
+            fee: NearToken::from_yoctonear(fee_yoctonear.0),
+        })
+    }
+
+    pub fn get_ai_processing_fee(&self) -> NearToken {
+        self.ai_processing_fee
+    }
+
+    pub fn get_max_ai_processing_fee(&self) -> NearToken {
+        MAX_AI_PROCESSING_FEE
+    }
+
+    /// Queues a platform fee change; see `execute_action`. Existing escrows keep
+    /// the fee they were created with.
+    pub fn set_platform_fee(&mut self, fee_bps: u16) -> u64 {
+        self.require_role(Role::FeeManager);
+        assert!(fee_bps <= MAX_PLATFORM_FEE_BPS, "Platform fee exceeds the protocol maximum");
+        self.queue_action(GovernanceAction::SetPlatformFee { fee_bps })
+    }
+
+    pub fn get_platform_fee(&self) -> u16 {
+        self.platform_fee_bps
+    }
+
+    /// Queues a change to the operator's share of each AI fee; the rest goes to the
+    /// treasury. See `execute_action`.
+    pub fn set_ai_fee_operator_share(&mut self, share_bps: u16) -> u64 {
+        self.require_role(Role::FeeManager);
+        assert!(share_bps <= 10_000, "Share cannot exceed 10000 basis points");
+        self.queue_action(GovernanceAction::SetOperatorFeeShare { share_bps })
+    }
+
+    pub fn get_ai_fee_operator_share(&self) -> u16 {
+        self.ai_fee_operator_share_bps
+    }
+
+    /// Queues a change of the account that receives treasury withdrawals.
+    pub fn set_treasury(&mut self, account_id: AccountId) -> u64 {
         self.require_owner();
-        if !self.trusted_tee_addresses.contains(&address) {
-            self.trusted_tee_addresses.push(address);
+        self.queue_action(GovernanceAction::SetTreasury { account_id })
+    }
+
+    pub fn get_treasury(&self) -> &AccountId {
+        &self.treasury
+    }
+
+    pub fn get_treasury_balance(&self) -> NearToken {
+        self.treasury_balance
+    }
+
+    /// Sends accrued fees to the treasury account; the whole balance if no amount is given.
+    pub fn withdraw_treasury(&mut self, amount: Option<U128>) {
+        self.require_not_paused(PauseScope::Releases);
+        if env::predecessor_account_id() != self.treasury {
+            self.require_role(Role::FeeManager);
         }
+        let amount = amount.map(|a| a.0).unwrap_or(self.treasury_balance.as_yoctonear());
+        assert!(amount > 0, "Nothing to withdraw");
+        assert!(
+            amount <= self.treasury_balance.as_yoctonear(),
+            "Amount exceeds treasury balance"
+        );
+
+        self.treasury_balance =
+            NearToken::from_yoctonear(self.treasury_balance.as_yoctonear() - amount);
+        let _ = Promise::new(self.treasury.clone()).transfer(NearToken::from_yoctonear(amount));
+
+        emit_event!("treasury_withdrawn", {
+            "treasury" => self.treasury,
+            "amount" => amount
+        });
     }
 
-    pub fn remove_tee_address(&mut self, address: Vec<u8>) {
+    /// Queues a new AI resolution timeout; returns the action id.
+    pub fn set_ai_resolution_timeout(&mut self, timeout_ns: U64) -> u64 {
         self.require_owner();
-        self.trusted_tee_addresses.retain(|a| a != &address);
+        assert!(timeout_ns.0 > 0, "Timeout must be greater than zero");
+        self.queue_action(GovernanceAction::SetAiResolutionTimeout { timeout_ns: timeout_ns.0 })
     }
 
-    pub fn get_trusted_tee_addresses(&self) -> &[Vec<u8>] {
-        &self.trusted_tee_addresses
+    pub fn get_ai_resolution_timeout(&self) -> U64 {
+        U64(self.ai_resolution_timeout_ns)
     }
 
-    pub fn set_ai_processing_fee(&mut self, fee_yoctonear: U128) {
+    /// Queues new bounds on the windows escrows may choose. The defaults must stay
+    /// inside them so that `create_contract` without explicit windows always succeeds.
+    pub fn set_time_window_bounds(&mut self, min: TimeWindows, max: TimeWindows) -> u64 {
         self.require_owner();
-        self.ai_processing_fee = NearToken::from_yoctonear(fee_yoctonear.0);
+        assert!(
+            default_time_windows().within(&min, &max),
+            "Bounds must include the default time windows"
+        );
+        assert!(min.payment_cooldown_ns > 0, "Payment cooldown minimum must be non-zero");
+        self.queue_action(GovernanceAction::SetTimeWindowBounds { min, max })
     }
 
-    pub fn get_ai_processing_fee(&self) -> NearToken {
-        self.ai_processing_fee
+    pub fn get_time_window_bounds(&self) -> (TimeWindows, TimeWindows) {
+        (self.min_time_windows.clone(), self.max_time_windows.clone())
+    }
+
+    /// Queues a new fallback arbitrator; time-locked like TEE registration, since
+    /// the fallback decides every escalated dispute.
+    pub fn set_fallback_arbitrator(&mut self, arbitrator: Option<AccountId>) -> u64 {
+        self.require_owner();
+        self.queue_action(GovernanceAction::SetFallbackArbitrator { account_id: arbitrator })
+    }
+
+    pub fn get_fallback_arbitrator(&self) -> Option<AccountId> {
+        self.fallback_arbitrator.clone()
     }
 
     #[payable]
@@ -107,7 +477,11 @@ impl Contract {
         security_deposit_pct: u8,
         prompt_hash: String,
         model_id: String,
+        fallback_policy: Option<FallbackPolicy>,
+        arbitrator: Option<Arbitrator>,
+        time_windows: Option<TimeWindows>,
     ) -> String {
+        self.require_not_paused(PauseScope::NewContracts);
         let deposit = env::attached_deposit();
         assert!(
             deposit >= NearToken::from_millinear(50),
@@ -119,15 +493,47 @@ impl Contract {
         );
         assert!(!milestones.is_empty(), "At least one milestone required");
 
+        let fallback_policy = fallback_policy.unwrap_or_default();
+        if fallback_policy == FallbackPolicy::Arbitrator {
+            assert!(
+                self.fallback_arbitrator.is_some(),
+                "No fallback arbitrator appointed"
+            );
+        }
+
+        let time_windows = time_windows.unwrap_or_else(default_time_windows);
+        assert!(
+            time_windows.within(&self.min_time_windows, &self.max_time_windows),
+            "Time windows are outside the allowed bounds"
+        );
+
         let client = env::predecessor_account_id();
         assert!(
             freelancer.as_ref() != Some(&client),
             "Cannot be your own freelancer"
         );
 
+        let arbitrator = arbitrator.unwrap_or_default();
+        if let Some(account) = arbitrator.account_id() {
+            assert!(
+                *account != client && freelancer.as_ref() != Some(account),
+                "Arbitrator cannot be a contract party"
+            );
+        }
+
         self.next_id += 1;
         let contract_id = format!("c{}", self.next_id);
 
+        assert!(
+            milestones.iter().all(|m| m.revision_limit <= MAX_REVISION_LIMIT),
+            "Revision limit exceeds the maximum"
+        );
+        assert!(
+            milestones
+                .iter()
+                .all(|m| m.due_date_ns.is_none_or(|d| d.0 > env::block_timestamp())),
+            "Milestone due date must be in the future"
+        );
         let total_amount: u128 = milestones.iter().map(|m| m.amount.0).sum();
         assert!(total_amount > 0, "Total amount must be greater than zero");
 
@@ -153,6 +559,12 @@ impl Contract {
                 status: MilestoneStatus::NotFunded,
                 payment_request_deadline_ns: None,
                 payment_request_blocked_until_ns: None,
+                deliverables: vec![],
+                revision_limit: m.revision_limit,
+                revision_requests: vec![],
+                due_date_ns: m.due_date_ns.map(|d| d.0),
+                extensions: vec![],
+                overrides_used: 0,
             })
             .collect();
 
@@ -179,6 +591,12 @@ impl Contract {
             disputes: vec![],
             model_id,
             security_pool: NearToken::from_yoctonear(0),
+            fallback_policy,
+            arbitrator,
+            ai_fee: self.ai_processing_fee,
+            platform_fee_bps: self.platform_fee_bps,
+            time_windows,
+            hold: None,
         };
 
         self.contracts.insert(contract_id.clone(),escrow);
@@ -196,6 +614,7 @@ impl Contract {
     }
 
     pub fn join_contract(&mut self, contract_id: String, invite_token: String) {
+        self.require_not_paused(PauseScope::NewContracts);
         let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
 
         assert!(contract.freelancer.is_none(), "Contract already has a freelancer");
@@ -243,15 +662,83 @@ impl Contract {
         })
     }
 
+    /// Prompt inputs for the open dispute on a milestone: the claim, both parties'
+    /// statements and the evidence set the ruling must commit to.
+    pub fn get_dispute_context(
+        &self,
+        contract_id: String,
+        milestone_id: String,
+    ) -> Option<DisputeContext> {
+        let contract = self.contracts.get(&contract_id)?;
+        let dispute = contract.latest_dispute(&milestone_id)?;
+        let milestone = &contract.milestones[contract.find_milestone(&milestone_id)?];
+        Some(DisputeContext {
+            contract_id,
+            milestone_id,
+            milestone_title: milestone.title.clone(),
+            milestone_description: milestone.description.clone(),
+            amount: milestone.amount,
+            raised_by: dispute.raised_by.clone(),
+            reason: dispute.reason.clone(),
+            category: dispute.category.clone(),
+            claimed_remedy: dispute.claimed_remedy.clone(),
+            deliverable: milestone.deliverables.last().cloned(),
+            statements: dispute.statements.clone(),
+            evidence: dispute.evidence.clone(),
+            evidence_root: evidence::evidence_root(&dispute.evidence).map(|r| evidence::to_hex(&r)),
+            prompt_hash: contract.prompt_hash.clone(),
+            model_id: contract.model_id.clone(),
+        })
+    }
+
+    pub fn get_resolution_acceptance(
+        &self,
+        contract_id: String,
+        milestone_id: String,
+    ) -> Option<ResolutionAcceptance> {
+        self.contracts
+            .get(&contract_id)
+            .and_then(|c| c.latest_dispute(&milestone_id))
+            .map(|d| ResolutionAcceptance {
+                client_accepted: d.client_accepted,
+                freelancer_accepted: d.freelancer_accepted,
+                client_waived: d.client_waived(),
+                freelancer_waived: d.freelancer_waived(),
+            })
+    }
+
+    /// Milestones the client can reclaim or dispute for non-delivery.
+    pub fn get_overdue_milestones(&self, contract_id: String) -> Vec<Milestone> {
+        let now = env::block_timestamp();
+        self.contracts
+            .get(&contract_id)
+            .map(|c| {
+                c.milestones
+                    .iter()
+                    .filter(|m| m.is_overdue_at(now, OVERDUE_GRACE_NS))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     pub fn get_prompt_hash(&self, contract_id: String) -> Option<String> {
         self.contracts.get(&contract_id).map(|c| c.prompt_hash.clone())
     }
 
+    /// Pending TEE-arbitrated disputes an agent can pick up. Disputes still in their
+    /// response window or under an active lease are skipped.
     pub fn get_pending_disputes(&self) -> Vec<(String, String)> {
+        let now = env::block_timestamp();
         let mut result = vec![];
         for (contract_id, contract) in self.contracts.iter() {
+            if contract.arbitrator != Arbitrator::Tee {
+                continue;
+            }
             for dispute in &contract.disputes {
-                if dispute.status == DisputeStatus::Pending {
+                if dispute.status == DisputeStatus::Pending
+                    && dispute.response_window_closed_at(now)
+                    && !dispute.is_leased_at(now) {
                     result.push((contract_id.clone(), dispute.milestone_id.clone()));
                 }
             }
@@ -266,6 +753,24 @@ pub struct MilestoneInput {
     pub title: String,
     pub description: String,
     pub amount: U128,
+    #[serde(default = "default_revision_limit")]
+    pub revision_limit: u8,
+    #[serde(default)]
+    pub due_date_ns: Option<U64>,
+}
+
+const HOUR_NS: u64 = 60 * 60 * 1_000_000_000;
+
+fn default_time_windows() -> TimeWindows {
+    TimeWindows {
+        payment_review_ns: PAYMENT_REQUEST_DEADLINE_NS,
+        dispute_review_ns: DISPUTE_DEADLINE_NS,
+        payment_cooldown_ns: PAYMENT_COOLDOWN_NS,
+    }
+}
+
+fn default_revision_limit() -> u8 {
+    DEFAULT_REVISION_LIMIT
 }
 
 #[cfg(test)]
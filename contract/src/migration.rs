@@ -0,0 +1,247 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::store::IterableMap;
+use near_sdk::{env, near_bindgen, AccountId, NearToken};
+
+use crate::dispute::DISPUTE_DEADLINE_NS;
+use crate::milestone::DEFAULT_REVISION_LIMIT;
+use crate::types::*;
+use crate::{default_time_windows, Contract, ContractExt};
+
+/// Layout written by `new` and `migrate`. Version 0 is the original release,
+/// which predates this key.
+pub(crate) const STATE_VERSION: u32 = 1;
+const STATE_VERSION_KEY: &[u8] = b"v";
+
+pub(crate) fn state_version() -> u32 {
+    env::storage_read(STATE_VERSION_KEY)
+        .map(|v| u32::try_from_slice(&v).expect("Corrupt state version"))
+        .unwrap_or(0)
+}
+
+pub(crate) fn write_state_version() {
+    env::storage_write(STATE_VERSION_KEY, &STATE_VERSION.to_le_bytes());
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct ContractV0 {
+    pub contracts: IterableMap<String, EscrowContractV0>,
+    pub account_contracts: IterableMap<AccountId, Vec<String>>,
+    pub trusted_tee_addresses: Vec<Vec<u8>>,
+    pub owner: AccountId,
+    pub next_id: u64,
+    pub ai_processing_fee: NearToken,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct EscrowContractV0 {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub client: AccountId,
+    pub freelancer: Option<AccountId>,
+    pub total_amount: NearToken,
+    pub funded_amount: NearToken,
+    pub security_deposit_pct: u8,
+    pub milestones: Vec<MilestoneV0>,
+    pub status: ContractStatus,
+    pub created_at: u64,
+    pub invite_token: Option<String>,
+    pub prompt_hash: String,
+    pub disputes: Vec<DisputeV0>,
+    pub model_id: String,
+    pub security_pool: NearToken,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct MilestoneV0 {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub amount: NearToken,
+    pub status: MilestoneStatus,
+    pub payment_request_deadline_ns: Option<u64>,
+    pub payment_request_blocked_until_ns: Option<u64>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct DisputeV0 {
+    pub milestone_id: String,
+    pub raised_by: AccountId,
+    pub reason: String,
+    pub status: DisputeStatus,
+    pub resolution: Option<Resolution>,
+    pub explanation: Option<String>,
+    pub deadline_ns: Option<u64>,
+    pub ai_fee_deducted: bool,
+    pub tee_signature: Option<Vec<u8>>,
+    pub tee_signing_address: Option<Vec<u8>>,
+    pub tee_text: Option<String>,
+    pub funds_released: bool,
+}
+
+impl MilestoneV0 {
+    fn upgrade(self) -> Milestone {
+        Milestone {
+            id: self.id,
+            title: self.title,
+            description: self.description,
+            amount: self.amount,
+            status: self.status,
+            payment_request_deadline_ns: self.payment_request_deadline_ns,
+            payment_request_blocked_until_ns: self.payment_request_blocked_until_ns,
+            deliverables: vec![],
+            revision_limit: DEFAULT_REVISION_LIMIT,
+            revision_requests: vec![],
+            due_date_ns: None,
+            extensions: vec![],
+            overrides_used: 0,
+        }
+    }
+}
+
+impl DisputeV0 {
+    /// Legacy disputes had no claim, so they keep the unbounded full-refund remedy;
+    /// pending ones get a fresh AI timeout from the moment of migration. The original
+    /// release paid the whole AI fee to the owner at resolution, so none is held.
+    fn upgrade(self, ai_fee: NearToken, ai_timeout_ns: u64) -> Dispute {
+        let pending = self.status == DisputeStatus::Pending;
+        let paid_fee = if self.ai_fee_deducted { ai_fee } else { NearToken::from_yoctonear(0) };
+        Dispute {
+            milestone_id: self.milestone_id,
+            raised_by: self.raised_by,
+            reason: self.reason,
+            category: DisputeCategory::Quality,
+            claimed_remedy: ClaimedRemedy::FullRefund,
+            status: self.status,
+            resolution: self.resolution,
+            explanation: self.explanation,
+            resolved_at_ns: self.deadline_ns.map(|d| d.saturating_sub(DISPUTE_DEADLINE_NS)),
+            deadline_ns: self.deadline_ns,
+            ai_fee_deducted: self.ai_fee_deducted,
+            ai_fee_amount: paid_fee,
+            ai_fee_operator: None,
+            ai_fee_operator_share: paid_fee,
+            tee_signature: self.tee_signature,
+            tee_signing_address: self.tee_signing_address,
+            tee_text: self.tee_text,
+            funds_released: self.funds_released,
+            client_accepted: false,
+            freelancer_accepted: false,
+            settlement: None,
+            resolution_deadline_ns: pending.then(|| env::block_timestamp() + ai_timeout_ns),
+            escalated_to: None,
+            fallback_applied: false,
+            jury: None,
+            lease: None,
+            evidence: vec![],
+            evidence_deadline_ns: None,
+            statements: vec![],
+            response_deadline_ns: None,
+            client_waived_response: false,
+            freelancer_waived_response: false,
+        }
+    }
+}
+
+impl EscrowContractV0 {
+    fn upgrade(self, ai_fee: NearToken, ai_timeout_ns: u64) -> EscrowContract {
+        EscrowContract {
+            id: self.id,
+            title: self.title,
+            description: self.description,
+            client: self.client,
+            freelancer: self.freelancer,
+            total_amount: self.total_amount,
+            funded_amount: self.funded_amount,
+            security_deposit_pct: self.security_deposit_pct,
+            milestones: self.milestones.into_iter().map(MilestoneV0::upgrade).collect(),
+            status: self.status,
+            created_at: self.created_at,
+            invite_token: self.invite_token,
+            prompt_hash: self.prompt_hash,
+            disputes: self
+                .disputes
+                .into_iter()
+                .map(|d| d.upgrade(ai_fee, ai_timeout_ns))
+                .collect(),
+            model_id: self.model_id,
+            security_pool: self.security_pool,
+            fallback_policy: FallbackPolicy::default(),
+            arbitrator: Arbitrator::default(),
+            ai_fee,
+            platform_fee_bps: 0,
+            time_windows: default_time_windows(),
+            hold: None,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Rewrites the stored state into the current layout; `update_contract` calls
+    /// it right after deploying. Escrows are moved over by `migrate_escrows`.
+    /// Original TEE keys carry no attestation or model list and must be
+    /// re-registered before they can sign.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        match state_version() {
+            0 => {
+                let old: ContractV0 = env::state_read().expect("No state to migrate");
+                let now = env::block_timestamp();
+                let mut state = Self::new(old.owner.clone());
+                state.tee_keys = old
+                    .trusted_tee_addresses
+                    .into_iter()
+                    .map(|public_key| TeeKey {
+                        public_key,
+                        attestation_hash: String::new(),
+                        model_ids: vec![],
+                        valid_from_ns: now,
+                        valid_until_ns: None,
+                        operator: old.owner.clone(),
+                        registered_at_ns: now,
+                    })
+                    .collect();
+                state.account_contracts = old.account_contracts;
+                state.next_id = old.next_id;
+                state.ai_processing_fee = old.ai_processing_fee;
+                state.legacy_contracts = Some(old.contracts);
+                state
+            }
+            STATE_VERSION => env::state_read().expect("No state to migrate"),
+            version => env::panic_str(&format!("Unknown state version {}", version)),
+        }
+    }
+
+    /// Moves up to `limit` escrows from the original layout into the current one
+    /// and returns how many are left. Escrows are unreachable until moved.
+    pub fn migrate_escrows(&mut self, limit: u32) -> u32 {
+        let ai_fee = self.ai_processing_fee;
+        let ai_timeout_ns = self.ai_resolution_timeout_ns;
+        let legacy = self.legacy_contracts.as_mut().expect("No escrows left to migrate");
+        let ids: Vec<String> = legacy.keys().take(limit as usize).cloned().collect();
+        for id in ids.iter() {
+            let escrow = legacy.remove(id).expect("Legacy escrow not found");
+            self.contracts.insert(id.clone(), escrow.upgrade(ai_fee, ai_timeout_ns));
+        }
+        let remaining = legacy.len();
+        if remaining == 0 {
+            self.legacy_contracts = None;
+        }
+
+        emit_event!("escrows_migrated", {
+            "count" => ids.len(),
+            "remaining" => remaining
+        });
+        remaining
+    }
+
+    pub fn get_state_version(&self) -> u32 {
+        state_version()
+    }
+}
@@ -1,14 +1,20 @@
-use near_sdk::{env, near_bindgen, Promise};
+use near_sdk::{env, near_bindgen, NearToken};
 
 use crate::types::*;
 use crate::{Contract, ContractExt};
 
-const PAYMENT_REQUEST_DEADLINE_NS: u64 = 48 * 60 * 60 * 1_000_000_000;
+pub(crate) const PAYMENT_REQUEST_DEADLINE_NS: u64 = 48 * 60 * 60 * 1_000_000_000;
+pub(crate) const OVERDUE_GRACE_NS: u64 = 3 * 24 * 60 * 60 * 1_000_000_000;
+pub(crate) const DEFAULT_REVISION_LIMIT: u8 = 2;
+pub(crate) const MAX_REVISION_LIMIT: u8 = 10;
+const MAX_URI_LEN: usize = 256;
+const MAX_NOTE_LEN: usize = 1_000;
 
 #[near_bindgen]
 impl Contract {
     pub fn start_milestone(&mut self, contract_id: String, milestone_id: String) {
         let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        contract.require_not_on_hold();
         let caller = env::predecessor_account_id();
         assert!(
             Some(&caller) == contract.freelancer.as_ref(),
@@ -30,8 +36,27 @@ impl Contract {
         });
     }
 
-    pub fn request_payment(&mut self, contract_id: String, milestone_id: String) {
+    /// Submits the milestone for review along with a commitment to the deliverable.
+    /// Every submission is kept, so disputes can point at the exact version.
+    pub fn request_payment(
+        &mut self,
+        contract_id: String,
+        milestone_id: String,
+        content_hash: Vec<u8>,
+        uri: String,
+        note: Option<String>,
+    ) {
+        assert_eq!(content_hash.len(), 32, "Content hash must be a 32-byte sha256 hash");
+        assert!(
+            !uri.is_empty() && uri.len() <= MAX_URI_LEN,
+            "Deliverable URI must be 1-256 bytes"
+        );
+        if let Some(note) = &note {
+            assert!(note.len() <= MAX_NOTE_LEN, "Deliverable note is too long");
+        }
+
         let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        contract.require_not_on_hold();
         let caller = env::predecessor_account_id();
         assert!(
             Some(&caller) == contract.freelancer.as_ref(),
@@ -52,21 +77,32 @@ impl Contract {
         }
         contract.milestones[idx].payment_request_blocked_until_ns = None;
 
-        contract.milestones[idx].status = MilestoneStatus::SubmittedForReview;
-        contract.milestones[idx].payment_request_deadline_ns =
-            Some(env::block_timestamp() + PAYMENT_REQUEST_DEADLINE_NS);
+        let milestone = &mut contract.milestones[idx];
+        let version = milestone.deliverables.len() as u32 + 1;
+        milestone.deliverables.push(Deliverable {
+            version,
+            content_hash,
+            uri,
+            note,
+            submitted_at_ns: env::block_timestamp(),
+        });
+        milestone.status = MilestoneStatus::SubmittedForReview;
+        milestone.payment_request_deadline_ns =
+            Some(env::block_timestamp() + contract.time_windows.payment_review_ns);
 
         self.contracts.insert(contract_id.clone(), contract);
 
         emit_event!("payment_requested", {
             "contract_id" => contract_id,
-            "milestone_id" => milestone_id
+            "milestone_id" => milestone_id,
+            "version" => version
         });
     }
 
     pub fn auto_approve_payment(&mut self, contract_id: String, milestone_id: String) {
+        self.require_not_paused(PauseScope::Releases);
         let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
-        let freelancer = contract.require_freelancer();
+        contract.require_not_on_hold();
 
         let idx = contract.find_milestone(&milestone_id).expect("Milestone not found");
         assert!(
@@ -90,10 +126,88 @@ impl Contract {
             contract.status = ContractStatus::Completed;
         }
 
+        let platform_fee = self.disburse(&contract, amount, NearToken::from_yoctonear(0));
         self.contracts.insert(contract_id.clone(), contract);
-        let _ = Promise::new(freelancer).transfer(amount);
 
         emit_event!("payment_auto_approved", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "amount" => amount.as_yoctonear(),
+            "platform_fee" => platform_fee.as_yoctonear()
+        });
+    }
+
+    /// Sends a submitted milestone back to the freelancer for fixes. Limited to the
+    /// milestone's revision allowance; after that the client must approve or dispute.
+    pub fn request_changes(
+        &mut self,
+        contract_id: String,
+        milestone_id: String,
+        feedback_hash: Vec<u8>,
+    ) {
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        contract.require_not_on_hold();
+        assert_eq!(
+            contract.client,
+            env::predecessor_account_id(),
+            "Only client can request changes"
+        );
+        assert_eq!(feedback_hash.len(), 32, "Feedback hash must be a 32-byte sha256 hash");
+
+        let idx = contract.find_milestone(&milestone_id).expect("Milestone not found");
+        let milestone = &mut contract.milestones[idx];
+        assert!(
+            milestone.status == MilestoneStatus::SubmittedForReview,
+            "Milestone must be submitted for review"
+        );
+        assert!(
+            milestone.revision_requests.len() < milestone.revision_limit as usize,
+            "Revision limit reached; approve or dispute the milestone"
+        );
+
+        milestone.revision_requests.push(RevisionRequest {
+            feedback_hash,
+            deliverable_version: milestone.deliverables.len() as u32,
+            requested_at_ns: env::block_timestamp(),
+        });
+        milestone.status = MilestoneStatus::InProgress;
+        milestone.payment_request_deadline_ns = None;
+        let revisions_left = milestone.revision_limit as usize - milestone.revision_requests.len();
+
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("changes_requested", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "revisions_left" => revisions_left
+        });
+    }
+
+    /// Returns an abandoned milestone's escrow to the client once it is past its
+    /// due date plus the grace period. Refunds are never blocked by a release pause.
+    pub fn reclaim_overdue_milestone(&mut self, contract_id: String, milestone_id: String) {
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        contract.require_not_on_hold();
+        assert_eq!(
+            contract.client,
+            env::predecessor_account_id(),
+            "Only client can reclaim a milestone"
+        );
+
+        let idx = contract.find_milestone(&milestone_id).expect("Milestone not found");
+        assert!(
+            contract.milestones[idx].is_overdue_at(env::block_timestamp(), OVERDUE_GRACE_NS),
+            "Milestone is not overdue"
+        );
+
+        let amount = contract.milestones[idx].amount;
+        contract.milestones[idx].status = MilestoneStatus::Refunded;
+        contract.restore_status();
+
+        self.disburse(&contract, NearToken::from_yoctonear(0), amount);
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("milestone_reclaimed", {
             "contract_id" => contract_id,
             "milestone_id" => milestone_id,
             "amount" => amount.as_yoctonear()
@@ -102,6 +216,7 @@ impl Contract {
 
     pub fn cancel_payment_request(&mut self, contract_id: String, milestone_id: String) {
         let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        contract.require_not_on_hold();
         let caller = env::predecessor_account_id();
         assert!(
             Some(&caller) == contract.freelancer.as_ref(),
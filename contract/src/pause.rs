@@ -0,0 +1,40 @@
+use near_sdk::{env, near_bindgen};
+
+use crate::types::*;
+use crate::{Contract, ContractExt};
+
+#[near_bindgen]
+impl Contract {
+    pub fn pause(&mut self, scope: PauseScope) {
+        self.require_role(Role::Pauser);
+        if !self.paused_scopes.contains(&scope) {
+            self.paused_scopes.push(scope);
+        }
+
+        emit_event!("paused", {
+            "scope" => format!("{:?}", scope)
+        });
+    }
+
+    pub fn unpause(&mut self, scope: PauseScope) {
+        self.require_role(Role::Pauser);
+        self.paused_scopes.retain(|s| *s != scope);
+
+        emit_event!("unpaused", {
+            "scope" => format!("{:?}", scope)
+        });
+    }
+
+    pub fn get_paused_scopes(&self) -> &[PauseScope] {
+        &self.paused_scopes
+    }
+
+    pub fn is_paused(&self, scope: PauseScope) -> bool {
+        self.paused_scopes.contains(&scope)
+    }
+
+    /// Refund-only exits are never gated; callers skip this check for them.
+    pub(crate) fn require_not_paused(&self, scope: PauseScope) {
+        assert!(!self.is_paused(scope), "Paused: {:?}", scope);
+    }
+}
@@ -0,0 +1,99 @@
+use near_sdk::{env, near_bindgen, AccountId, Gas, NearToken, Promise};
+
+use crate::types::*;
+use crate::{Contract, ContractExt};
+
+const MIGRATE_GAS: Gas = Gas::from_tgas(100);
+
+#[near_bindgen]
+impl Contract {
+    pub fn grant_role(&mut self, role: Role, account_id: AccountId) {
+        self.require_owner();
+        let mut members = self.roles.get(&role).cloned().unwrap_or_default();
+        if !members.contains(&account_id) {
+            members.push(account_id.clone());
+            self.roles.insert(role, members);
+        }
+
+        emit_event!("role_granted", {
+            "role" => format!("{:?}", role),
+            "account_id" => account_id
+        });
+    }
+
+    pub fn revoke_role(&mut self, role: Role, account_id: AccountId) {
+        self.require_owner();
+        let mut members = self.roles.get(&role).cloned().unwrap_or_default();
+        assert!(members.contains(&account_id), "Account does not hold this role");
+        members.retain(|a| a != &account_id);
+        self.roles.insert(role, members);
+
+        emit_event!("role_revoked", {
+            "role" => format!("{:?}", role),
+            "account_id" => account_id
+        });
+    }
+
+    pub fn get_role_members(&self, role: Role) -> Vec<AccountId> {
+        self.roles.get(&role).cloned().unwrap_or_default()
+    }
+
+    pub fn has_role(&self, role: Role, account_id: AccountId) -> bool {
+        account_id == self.owner
+            || self
+                .roles
+                .get(&role)
+                .is_some_and(|members| members.contains(&account_id))
+    }
+
+    /// Queues an upgrade to the WASM with this sha256 hash; returns the action id.
+    pub fn propose_update(&mut self, code_hash: Vec<u8>) -> u64 {
+        self.require_role(Role::Upgrader);
+        assert_eq!(code_hash.len(), 32, "Code hash must be a 32-byte sha256 hash");
+        self.queue_action(GovernanceAction::UpgradeContract { code_hash })
+    }
+
+    /// Deploys the WASM passed as raw call input over this contract. The code must
+    /// match an upgrade queued with `propose_update` whose time lock has passed;
+    /// `migrate` runs in the same batch, so a failed migration reverts the deploy.
+    pub fn update_contract(&mut self) -> Promise {
+        self.require_role(Role::Upgrader);
+        let code = env::input().expect("No contract code provided");
+        let code_hash = env::sha256(&code);
+
+        let idx = self
+            .queued_actions
+            .iter()
+            .position(|a| match &a.action {
+                GovernanceAction::UpgradeContract { code_hash: queued } => *queued == code_hash,
+                _ => false,
+            })
+            .expect("Code does not match a queued upgrade");
+        assert!(
+            env::block_timestamp() >= self.queued_actions[idx].executable_at_ns,
+            "Action is still time-locked"
+        );
+        let action_id = self.queued_actions.remove(idx).id;
+
+        emit_event!("governance_action_executed", {
+            "action_id" => action_id
+        });
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call(
+                "migrate".to_string(),
+                vec![],
+                NearToken::from_yoctonear(0),
+                MIGRATE_GAS,
+            )
+    }
+
+    /// The owner implicitly holds every role.
+    pub(crate) fn require_role(&self, role: Role) {
+        assert!(
+            self.has_role(role, env::predecessor_account_id()),
+            "Missing role: {:?}",
+            role
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use near_sdk::{env, near_bindgen};
+
+use crate::types::*;
+use crate::{Contract, ContractExt};
+
+pub(crate) const RESPONSE_WINDOW_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+const MAX_STATEMENTS_PER_PARTY: usize = 5;
+const MAX_STATEMENT_LEN: usize = 2_000;
+
+#[near_bindgen]
+impl Contract {
+    /// Files a party statement during the response window. Exactly one of `text`
+    /// (bounded in size) or `content_hash` (of a statement kept off-chain) is given.
+    pub fn file_statement(
+        &mut self,
+        contract_id: String,
+        milestone_id: String,
+        text: Option<String>,
+        content_hash: Option<Vec<u8>>,
+    ) {
+        let caller = env::predecessor_account_id();
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        assert!(contract.is_party(&caller), "Only contract parties can file statements");
+
+        match (&text, &content_hash) {
+            (Some(text), None) => assert!(
+                !text.is_empty() && text.len() <= MAX_STATEMENT_LEN,
+                "Statement must be 1-2000 bytes"
+            ),
+            (None, Some(hash)) => {
+                assert_eq!(hash.len(), 32, "Content hash must be a 32-byte sha256 hash")
+            }
+            _ => env::panic_str("Provide either a statement text or its hash"),
+        }
+
+        let is_client = caller == contract.client;
+        let dispute_idx = contract
+            .find_dispute(&milestone_id, DisputeStatus::Pending)
+            .expect("No active dispute for this milestone");
+        let dispute = &mut contract.disputes[dispute_idx];
+        let now = env::block_timestamp();
+        assert!(
+            !dispute.response_window_closed_at(now),
+            "Response window has closed"
+        );
+        let party_closed = if is_client {
+            dispute.client_waived_response
+        } else {
+            dispute.freelancer_waived_response
+        };
+        assert!(!party_closed, "Response window already waived");
+        assert!(
+            dispute.statements.iter().filter(|s| s.author == caller).count()
+                < MAX_STATEMENTS_PER_PARTY,
+            "Too many statements from this party"
+        );
+
+        dispute.statements.push(Statement {
+            author: caller.clone(),
+            text,
+            content_hash,
+            filed_at_ns: now,
+        });
+
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("statement_filed", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "account_id" => caller
+        });
+    }
+
+    /// Gives up the rest of the caller's response window. Once both parties have
+    /// waived, the dispute is handed to the arbitrator without waiting.
+    pub fn waive_response_window(&mut self, contract_id: String, milestone_id: String) {
+        let caller = env::predecessor_account_id();
+        let mut contract = self.contracts.get(&contract_id).cloned().expect("Contract not found");
+        assert!(contract.is_party(&caller), "Only contract parties can waive");
+        let is_client = caller == contract.client;
+
+        let dispute_idx = contract
+            .find_dispute(&milestone_id, DisputeStatus::Pending)
+            .expect("No active dispute for this milestone");
+        let dispute = &mut contract.disputes[dispute_idx];
+        assert!(
+            !dispute.response_window_closed_at(env::block_timestamp()),
+            "Response window has closed"
+        );
+        if is_client {
+            dispute.client_waived_response = true;
+        } else {
+            dispute.freelancer_waived_response = true;
+        }
+
+        self.contracts.insert(contract_id.clone(), contract);
+
+        emit_event!("response_window_waived", {
+            "contract_id" => contract_id,
+            "milestone_id" => milestone_id,
+            "account_id" => caller
+        });
+    }
+}
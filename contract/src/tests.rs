@@ -1,8 +1,10 @@
-use near_sdk::json_types::U128;
+use ed25519_dalek::{Signer, SigningKey};
+use near_sdk::json_types::{U128, U64};
 use near_sdk::test_utils::VMContextBuilder;
 use near_sdk::testing_env;
 use near_sdk::NearToken;
 
+use crate::jury::vote_commitment;
 use crate::types::*;
 use crate::{Contract, MilestoneInput};
 
@@ -26,11 +28,28 @@ fn setup_context(predecessor: &near_sdk::AccountId, deposit: u128) {
     testing_env!(context);
 }
 
+fn setup_context_at(predecessor: &near_sdk::AccountId, timestamp_ns: u64) {
+    let context = VMContextBuilder::new()
+        .predecessor_account_id(predecessor.clone())
+        .block_timestamp(timestamp_ns)
+        .build();
+    testing_env!(context);
+}
+
+const DAY_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
 fn create_test_contract() -> Contract {
     setup_context(&owner(), 0);
     Contract::new(owner())
 }
 
+fn set_fee_via_governance(contract: &mut Contract, fee: u128) {
+    setup_context(&owner(), 0);
+    let action_id = contract.set_ai_processing_fee(U128(fee));
+    setup_context_at(&owner(), DAY_NS);
+    contract.execute_action(action_id);
+}
+
 fn create_escrow_with_milestone(contract: &mut Contract) -> String {
     setup_context(&alice(), 50_000_000_000_000_000_000_000);
     contract.create_contract(
@@ -40,14 +59,80 @@ fn create_escrow_with_milestone(contract: &mut Contract) -> String {
             title: "Milestone 1".into(),
             description: "Build feature".into(),
             amount: U128(10_000_000_000_000_000_000_000_000), // 10 NEAR
+            revision_limit: 2,
+            due_date_ns: None,
         }],
         Some(bob()),
         10,
         "abc123hash".into(),
         "Qwen/Qwen3-30B-A3B-Instruct-2507".into(),
+        None,
+        None,
+        None,
     )
 }
 
+fn raise_test_dispute(contract: &mut Contract) -> String {
+    let id = create_escrow_with_milestone(contract);
+
+    setup_context(&alice(), 11_000_000_000_000_000_000_000_000);
+    contract.fund_contract(id.clone());
+
+    setup_context(&bob(), 0);
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
+
+    setup_context(&alice(), 0);
+    contract.raise_dispute(
+        id.clone(),
+        "m1".into(),
+        "Work incomplete".into(),
+        DisputeCategory::Quality,
+        ClaimedRemedy::FullRefund,
+    );
+    id
+}
+
+// Stands in for a TEE-signed `submit_ai_resolution`, which needs a real enclave key.
+fn resolve_test_dispute(contract: &mut Contract, id: &str, resolution: Resolution) {
+    let mut escrow = contract.get_contract(id.into()).unwrap();
+    let dispute = escrow.disputes.last_mut().unwrap();
+    dispute.status = DisputeStatus::AiResolved;
+    dispute.resolution = Some(resolution);
+    dispute.deadline_ns = Some(48 * 60 * 60 * 1_000_000_000);
+    contract.contracts.insert(id.into(), escrow);
+}
+
+fn tee_signing_key() -> SigningKey {
+    SigningKey::from_bytes(&[42u8; 32])
+}
+
+fn register_test_tee_key(contract: &mut Contract, operator: &near_sdk::AccountId) {
+    setup_context(&owner(), 0);
+    let action_id = contract.register_tee_address(
+        tee_signing_key().verifying_key().to_bytes().to_vec(),
+        "attestation-sha256".into(),
+        vec!["Qwen/Qwen3-30B-A3B-Instruct-2507".into()],
+        None,
+        None,
+        operator.clone(),
+    );
+    setup_context_at(&owner(), DAY_NS);
+    contract.execute_action(action_id);
+}
+
+fn submit_signed_resolution(contract: &mut Contract, id: &str, resolution: Resolution, tee_text: &str) {
+    let key = tee_signing_key();
+    contract.submit_ai_resolution(
+        id.into(),
+        "m1".into(),
+        resolution,
+        "AI explanation".into(),
+        key.sign(tee_text.as_bytes()).to_bytes().to_vec(),
+        key.verifying_key().to_bytes().to_vec(),
+        tee_text.into(),
+    );
+}
+
 #[test]
 fn test_create_contract() {
     let mut contract = create_test_contract();
@@ -73,11 +158,16 @@ fn test_create_contract_draft_without_freelancer() {
             title: "M1".into(),
             description: "D1".into(),
             amount: U128(1_000_000_000_000_000_000_000_000),
+            revision_limit: 2,
+            due_date_ns: None,
         }],
         None,
         10,
         "hash".into(),
         "model".into(),
+        None,
+        None,
+        None,
     );
 
     let escrow = contract.get_contract(id).unwrap();
@@ -93,11 +183,14 @@ fn test_invalid_security_pct() {
     contract.create_contract(
         "Bad".into(),
         "Desc".into(),
-        vec![MilestoneInput { title: "M".into(), description: "D".into(), amount: U128(1_000_000_000_000_000_000_000_000) }],
+        vec![MilestoneInput { title: "M".into(), description: "D".into(), amount: U128(1_000_000_000_000_000_000_000_000), revision_limit: 2, due_date_ns: None }],
         Some(bob()),
         50,
         "hash".into(),
         "model".into(),
+        None,
+        None,
+        None,
     );
 }
 
@@ -109,11 +202,14 @@ fn test_cannot_self_hire() {
     contract.create_contract(
         "Self".into(),
         "Desc".into(),
-        vec![MilestoneInput { title: "M".into(), description: "D".into(), amount: U128(1_000_000_000_000_000_000_000_000) }],
+        vec![MilestoneInput { title: "M".into(), description: "D".into(), amount: U128(1_000_000_000_000_000_000_000_000), revision_limit: 2, due_date_ns: None }],
         Some(alice()),
         10,
         "hash".into(),
         "model".into(),
+        None,
+        None,
+        None,
     );
 }
 
@@ -184,7 +280,7 @@ fn test_milestone_lifecycle() {
 
     // Freelancer requests payment (milestone is already InProgress after funding)
     setup_context(&bob(), 0);
-    contract.request_payment(id.clone(), "m1".into());
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
     assert_eq!(
         contract.get_contract(id.clone()).unwrap().milestones[0].status,
         MilestoneStatus::SubmittedForReview
@@ -221,7 +317,7 @@ fn test_cancel_payment_request() {
     contract.fund_contract(id.clone());
 
     setup_context(&bob(), 0);
-    contract.request_payment(id.clone(), "m1".into());
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
     contract.cancel_payment_request(id.clone(), "m1".into());
 
     assert_eq!(
@@ -234,8 +330,7 @@ fn test_cancel_payment_request() {
 fn test_raise_dispute() {
     let mut contract = create_test_contract();
 
-    setup_context(&owner(), 0);
-    contract.set_ai_processing_fee(U128(50_000_000_000_000_000_000_000)); // 0.05 NEAR
+    set_fee_via_governance(&mut contract, 50_000_000_000_000_000_000_000);
 
     let id = create_escrow_with_milestone(&mut contract);
 
@@ -243,10 +338,16 @@ fn test_raise_dispute() {
     contract.fund_contract(id.clone());
 
     setup_context(&bob(), 0);
-    contract.request_payment(id.clone(), "m1".into());
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
 
     setup_context(&alice(), 0);
-    contract.raise_dispute(id.clone(), "m1".into(), "Work incomplete".into());
+    contract.raise_dispute(
+        id.clone(),
+        "m1".into(),
+        "Work incomplete".into(),
+        DisputeCategory::Quality,
+        ClaimedRemedy::FullRefund,
+    );
 
     let escrow = contract.get_contract(id.clone()).unwrap();
     assert_eq!(escrow.milestones[0].status, MilestoneStatus::Disputed);
@@ -265,10 +366,16 @@ fn test_freelancer_cannot_raise_dispute() {
     contract.fund_contract(id.clone());
 
     setup_context(&bob(), 0);
-    contract.request_payment(id.clone(), "m1".into());
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
 
     setup_context(&bob(), 0);
-    contract.raise_dispute(id, "m1".into(), "Reason".into());
+    contract.raise_dispute(
+        id,
+        "m1".into(),
+        "Reason".into(),
+        DisputeCategory::Quality,
+        ClaimedRemedy::FullRefund,
+    );
 }
 
 #[test]
@@ -292,11 +399,14 @@ fn test_join_contract() {
     let id = contract.create_contract(
         "Open".into(),
         "Desc".into(),
-        vec![MilestoneInput { title: "M".into(), description: "D".into(), amount: U128(1_000_000_000_000_000_000_000_000) }],
+        vec![MilestoneInput { title: "M".into(), description: "D".into(), amount: U128(1_000_000_000_000_000_000_000_000), revision_limit: 2, due_date_ns: None }],
         None,
         10,
         "hash".into(),
         "model".into(),
+        None,
+        None,
+        None,
     );
 
     let token = contract.get_contract(id.clone()).unwrap().invite_token.unwrap();
@@ -323,8 +433,7 @@ fn test_get_prompt_hash() {
 fn test_get_pending_disputes() {
     let mut contract = create_test_contract();
 
-    setup_context(&owner(), 0);
-    contract.set_ai_processing_fee(U128(50_000_000_000_000_000_000_000));
+    set_fee_via_governance(&mut contract, 50_000_000_000_000_000_000_000);
 
     let id = create_escrow_with_milestone(&mut contract);
 
@@ -334,12 +443,1413 @@ fn test_get_pending_disputes() {
     contract.fund_contract(id.clone());
 
     setup_context(&bob(), 0);
-    contract.request_payment(id.clone(), "m1".into());
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
 
     setup_context(&alice(), 0);
-    contract.raise_dispute(id.clone(), "m1".into(), "Incomplete".into());
+    contract.raise_dispute(
+        id.clone(),
+        "m1".into(),
+        "Incomplete".into(),
+        DisputeCategory::Quality,
+        ClaimedRemedy::FullRefund,
+    );
+
+    assert!(contract.get_pending_disputes().is_empty());
 
+    setup_context_at(&alice(), DAY_NS);
     let pending = contract.get_pending_disputes();
     assert_eq!(pending.len(), 1);
     assert_eq!(pending[0], (id, "m1".to_string()));
 }
+
+#[test]
+fn test_accept_resolution_requires_both_parties() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+    resolve_test_dispute(&mut contract, &id, Resolution::Freelancer);
+
+    setup_context(&bob(), 0);
+    contract.accept_resolution(id.clone(), "m1".into());
+    assert_eq!(
+        contract.get_dispute(id.clone(), "m1".into()).unwrap().status,
+        DisputeStatus::AiResolved
+    );
+
+    let acceptance = contract.get_resolution_acceptance(id.clone(), "m1".into()).unwrap();
+    assert!(acceptance.freelancer_accepted);
+    assert!(!acceptance.freelancer_waived);
+
+    setup_context(&alice(), 0);
+    contract.accept_resolution(id.clone(), "m1".into());
+    assert_eq!(
+        contract.get_dispute(id.clone(), "m1".into()).unwrap().status,
+        DisputeStatus::Finalized
+    );
+    assert!(contract.get_resolution_acceptance(id, "m1".into()).unwrap().client_waived);
+}
+
+#[test]
+#[should_panic(expected = "Client waived the right to override by accepting the resolution")]
+fn test_client_acceptance_of_split_waives_override() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+    resolve_test_dispute(&mut contract, &id, Resolution::Split { freelancer_pct: 60 });
+
+    setup_context(&alice(), 0);
+    contract.accept_resolution(id.clone(), "m1".into());
+    contract.override_to_continue_work(id, "m1".into());
+}
+
+#[test]
+fn test_negotiated_settlement_skips_ai_fee() {
+    let mut contract = create_test_contract();
+
+    set_fee_via_governance(&mut contract, 50_000_000_000_000_000_000_000);
+
+    let id = raise_test_dispute(&mut contract);
+
+    setup_context(&bob(), 0);
+    contract.propose_settlement(id.clone(), "m1".into(), Resolution::Split { freelancer_pct: 70 });
+
+    setup_context(&alice(), 0);
+    contract.accept_settlement(id.clone(), "m1".into(), Resolution::Split { freelancer_pct: 70 });
+
+    let escrow = contract.get_contract(id).unwrap();
+    assert_eq!(escrow.disputes[0].status, DisputeStatus::Settled);
+    assert!(escrow.disputes[0].funds_released);
+    assert!(!escrow.disputes[0].ai_fee_deducted);
+    assert_eq!(escrow.security_pool.as_yoctonear(), 1_000_000_000_000_000_000_000_000);
+    assert_eq!(escrow.milestones[0].status, MilestoneStatus::Completed);
+    assert_eq!(escrow.status, ContractStatus::Resolved);
+}
+
+#[test]
+#[should_panic(expected = "Cannot accept your own settlement proposal")]
+fn test_cannot_accept_own_settlement() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+
+    setup_context(&alice(), 0);
+    contract.propose_settlement(id.clone(), "m1".into(), Resolution::Client);
+    contract.accept_settlement(id, "m1".into(), Resolution::Client);
+}
+
+#[test]
+#[should_panic(expected = "Settlement proposal has changed")]
+fn test_accept_settlement_rejects_replaced_proposal() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+
+    setup_context(&bob(), 0);
+    contract.propose_settlement(id.clone(), "m1".into(), Resolution::Split { freelancer_pct: 30 });
+    contract.propose_settlement(id.clone(), "m1".into(), Resolution::Freelancer);
+
+    setup_context(&alice(), 0);
+    contract.accept_settlement(id, "m1".into(), Resolution::Split { freelancer_pct: 30 });
+}
+
+#[test]
+fn test_withdraw_dispute_restores_review() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+
+    setup_context(&alice(), 0);
+    contract.withdraw_dispute(id.clone(), "m1".into(), false);
+
+    let escrow = contract.get_contract(id).unwrap();
+    assert_eq!(escrow.disputes[0].status, DisputeStatus::Withdrawn);
+    assert_eq!(escrow.milestones[0].status, MilestoneStatus::SubmittedForReview);
+    assert!(escrow.milestones[0].payment_request_deadline_ns.is_some());
+    assert_eq!(escrow.status, ContractStatus::Active);
+    assert!(contract.get_pending_disputes().is_empty());
+}
+
+#[test]
+fn test_withdraw_dispute_with_approval() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+
+    setup_context(&alice(), 0);
+    contract.withdraw_dispute(id.clone(), "m1".into(), true);
+
+    let escrow = contract.get_contract(id).unwrap();
+    assert_eq!(escrow.milestones[0].status, MilestoneStatus::Completed);
+    assert_eq!(escrow.status, ContractStatus::Completed);
+}
+
+#[test]
+#[should_panic(expected = "Only the party who raised the dispute can withdraw it")]
+fn test_freelancer_cannot_withdraw_dispute() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+
+    setup_context(&bob(), 0);
+    contract.withdraw_dispute(id, "m1".into(), false);
+}
+
+#[test]
+fn test_fallback_refunds_client_after_timeout() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+
+    setup_context_at(&bob(), 8 * DAY_NS);
+    contract.trigger_fallback(id.clone(), "m1".into());
+
+    let dispute = contract.get_dispute(id.clone(), "m1".into()).unwrap();
+    assert_eq!(dispute.status, DisputeStatus::Finalized);
+    assert_eq!(dispute.resolution, Some(Resolution::Client));
+    assert!(dispute.fallback_applied);
+    assert!(!dispute.ai_fee_deducted);
+
+    contract.release_dispute_funds(id.clone(), "m1".into());
+    assert_eq!(contract.get_contract(id).unwrap().status, ContractStatus::Resolved);
+}
+
+#[test]
+#[should_panic(expected = "Resolution deadline has not passed")]
+fn test_fallback_before_timeout_rejected() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+
+    setup_context_at(&alice(), DAY_NS);
+    contract.trigger_fallback(id, "m1".into());
+}
+
+fn escalate_test_dispute(contract: &mut Contract, arbitrator: &near_sdk::AccountId) -> String {
+    setup_context(&owner(), 0);
+    let action_id = contract.set_fallback_arbitrator(Some(arbitrator.clone()));
+    assert_eq!(contract.get_fallback_arbitrator(), None);
+    setup_context_at(&owner(), DAY_NS);
+    contract.execute_action(action_id);
+
+    setup_context(&alice(), 50_000_000_000_000_000_000_000);
+    let id = contract.create_contract(
+        "Escalating".into(),
+        "Desc".into(),
+        vec![MilestoneInput { title: "M".into(), description: "D".into(), amount: U128(1_000_000_000_000_000_000_000_000), revision_limit: 2, due_date_ns: None }],
+        Some(bob()),
+        10,
+        "hash".into(),
+        "model".into(),
+        Some(FallbackPolicy::Arbitrator),
+        None,
+        None,
+    );
+
+    setup_context(&alice(), 1_100_000_000_000_000_000_000_000);
+    contract.fund_contract(id.clone());
+    setup_context(&bob(), 0);
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
+    setup_context(&alice(), 0);
+    contract.raise_dispute(
+        id.clone(),
+        "m1".into(),
+        "No response".into(),
+        DisputeCategory::Quality,
+        ClaimedRemedy::FullRefund,
+    );
+
+    setup_context_at(&alice(), 8 * DAY_NS);
+    contract.trigger_fallback(id.clone(), "m1".into());
+    assert_eq!(
+        contract.get_dispute(id.clone(), "m1".into()).unwrap().status,
+        DisputeStatus::Escalated
+    );
+    id
+}
+
+#[test]
+fn test_fallback_escalates_to_arbitrator() {
+    let mut contract = create_test_contract();
+    let arbitrator: near_sdk::AccountId = "arbitrator.testnet".parse().unwrap();
+    let id = escalate_test_dispute(&mut contract, &arbitrator);
+
+    setup_context_at(&arbitrator, 9 * DAY_NS);
+    contract.submit_arbitrator_resolution(
+        id.clone(),
+        "m1".into(),
+        Resolution::Split { freelancer_pct: 40 },
+        "Partial delivery".into(),
+    );
+
+    let dispute = contract.get_dispute(id, "m1".into()).unwrap();
+    assert_eq!(dispute.status, DisputeStatus::AiResolved);
+    assert_eq!(dispute.resolution, Some(Resolution::Split { freelancer_pct: 40 }));
+}
+
+#[test]
+fn test_stale_escalation_falls_back_to_split() {
+    let mut contract = create_test_contract();
+    let arbitrator: near_sdk::AccountId = "arbitrator.testnet".parse().unwrap();
+    let id = escalate_test_dispute(&mut contract, &arbitrator);
+
+    setup_context_at(&bob(), 22 * DAY_NS);
+    contract.trigger_fallback(id.clone(), "m1".into());
+
+    let dispute = contract.get_dispute(id, "m1".into()).unwrap();
+    assert_eq!(dispute.status, DisputeStatus::Finalized);
+    assert_eq!(dispute.resolution, Some(Resolution::Split { freelancer_pct: 50 }));
+}
+
+#[test]
+#[should_panic(expected = "Resolution deadline has not passed")]
+fn test_escalation_cannot_fall_back_before_deadline() {
+    let mut contract = create_test_contract();
+    let arbitrator: near_sdk::AccountId = "arbitrator.testnet".parse().unwrap();
+    let id = escalate_test_dispute(&mut contract, &arbitrator);
+
+    setup_context_at(&bob(), 21 * DAY_NS);
+    contract.trigger_fallback(id, "m1".into());
+}
+
+#[test]
+fn test_human_arbitrator_resolves_dispute() {
+    let mut contract = create_test_contract();
+    let arbitrator: near_sdk::AccountId = "judge.testnet".parse().unwrap();
+
+    setup_context(&alice(), 50_000_000_000_000_000_000_000);
+    let id = contract.create_contract(
+        "Human".into(),
+        "Desc".into(),
+        vec![MilestoneInput { title: "M".into(), description: "D".into(), amount: U128(1_000_000_000_000_000_000_000_000), revision_limit: 2, due_date_ns: None }],
+        Some(bob()),
+        10,
+        "hash".into(),
+        "model".into(),
+        None,
+        Some(Arbitrator::Human { account_id: arbitrator.clone() }),
+        None,
+    );
+
+    setup_context(&alice(), 1_100_000_000_000_000_000_000_000);
+    contract.fund_contract(id.clone());
+    setup_context(&bob(), 0);
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
+    setup_context(&alice(), 0);
+    contract.raise_dispute(
+        id.clone(),
+        "m1".into(),
+        "Wrong colors".into(),
+        DisputeCategory::Quality,
+        ClaimedRemedy::FullRefund,
+    );
+
+    setup_context_at(&arbitrator, DAY_NS);
+    assert!(contract.get_pending_disputes().is_empty());
+    contract.submit_arbitrator_resolution(
+        id.clone(),
+        "m1".into(),
+        Resolution::Freelancer,
+        "Matches the spec".into(),
+    );
+
+    let dispute = contract.get_dispute(id, "m1".into()).unwrap();
+    assert_eq!(dispute.status, DisputeStatus::AiResolved);
+    assert_eq!(dispute.resolution, Some(Resolution::Freelancer));
+}
+
+#[test]
+#[should_panic(expected = "Only the assigned arbitrator can resolve this dispute")]
+fn test_stranger_cannot_arbitrate() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+
+    let stranger: near_sdk::AccountId = "stranger.testnet".parse().unwrap();
+    setup_context(&stranger, 0);
+    contract.submit_arbitrator_resolution(id, "m1".into(), Resolution::Client, "".into());
+}
+
+#[test]
+fn test_jury_appeal_overturns_resolution() {
+    let mut contract = create_test_contract();
+    let jurors: Vec<near_sdk::AccountId> = ["j1.testnet", "j2.testnet", "j3.testnet"]
+        .iter()
+        .map(|a| a.parse().unwrap())
+        .collect();
+    for juror in &jurors {
+        setup_context(juror, 5_000_000_000_000_000_000_000_000);
+        contract.stake_as_juror();
+    }
+
+    let id = raise_test_dispute(&mut contract);
+    resolve_test_dispute(&mut contract, &id, Resolution::Freelancer);
+
+    setup_context(&alice(), 1_000_000_000_000_000_000_000_000);
+    contract.appeal_to_jury(id.clone(), "m1".into());
+    assert_eq!(
+        contract.get_dispute(id.clone(), "m1".into()).unwrap().status,
+        DisputeStatus::Appealed
+    );
+
+    let votes = [Resolution::Client, Resolution::Client, Resolution::Freelancer];
+    for (juror, vote) in jurors.iter().zip(votes.iter()) {
+        setup_context_at(juror, DAY_NS);
+        contract.commit_vote(id.clone(), "m1".into(), vote_commitment(vote, "salt"));
+    }
+    for (juror, vote) in jurors.iter().zip(votes.iter()) {
+        setup_context_at(juror, 2 * DAY_NS + 1);
+        contract.reveal_vote(id.clone(), "m1".into(), vote.clone(), "salt".into());
+    }
+
+    setup_context_at(&bob(), 3 * DAY_NS);
+    contract.tally_jury(id.clone(), "m1".into());
+
+    let dispute = contract.get_dispute(id, "m1".into()).unwrap();
+    assert_eq!(dispute.status, DisputeStatus::Finalized);
+    assert_eq!(dispute.resolution, Some(Resolution::Client));
+
+    let stakes: Vec<u128> = jurors
+        .iter()
+        .map(|j| contract.get_juror(j.clone()).unwrap().stake.as_yoctonear())
+        .collect();
+    assert_eq!(stakes[2], 4_500_000_000_000_000_000_000_000);
+    assert_eq!(stakes[0], 5_750_000_000_000_000_000_000_000);
+    assert_eq!(stakes[1], 5_750_000_000_000_000_000_000_000);
+}
+
+#[test]
+#[should_panic(expected = "Only the losing party can appeal")]
+fn test_winner_cannot_appeal() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+    resolve_test_dispute(&mut contract, &id, Resolution::Freelancer);
+
+    setup_context(&bob(), 1_000_000_000_000_000_000_000_000);
+    contract.appeal_to_jury(id, "m1".into());
+}
+
+#[test]
+fn test_tee_registry_validity_window() {
+    let mut contract = create_test_contract();
+
+    setup_context(&owner(), 0);
+    let action_id = contract.register_tee_address(
+        vec![7u8; 32],
+        "attestation-sha256".into(),
+        vec!["Qwen/Qwen3-30B-A3B-Instruct-2507".into()],
+        None,
+        Some(near_sdk::json_types::U64(3 * DAY_NS)),
+        owner(),
+    );
+    assert!(contract.get_tee_registry().is_empty());
+
+    setup_context_at(&owner(), DAY_NS);
+    contract.execute_action(action_id);
+
+    let registry = contract.get_tee_registry();
+    assert_eq!(registry.len(), 1);
+    assert_eq!(registry[0].valid_from_ns, DAY_NS);
+    assert!(registry[0].allows_model("Qwen/Qwen3-30B-A3B-Instruct-2507"));
+    assert!(!registry[0].allows_model("openai/gpt-oss-120b"));
+    assert_eq!(contract.get_trusted_tee_addresses().len(), 1);
+
+    setup_context_at(&owner(), 3 * DAY_NS);
+    assert!(contract.get_trusted_tee_addresses().is_empty());
+}
+
+fn revoke_test_tee_key(contract: &mut Contract, compromised_at_ns: u64) {
+    setup_context_at(&owner(), DAY_NS);
+    let action_id = contract.remove_tee_address(
+        tee_signing_key().verifying_key().to_bytes().to_vec(),
+        Some(U64(compromised_at_ns)),
+    );
+    setup_context_at(&owner(), 2 * DAY_NS);
+    contract.execute_action(action_id);
+}
+
+fn invalidate_revoked_key(contract: &mut Contract) -> u32 {
+    setup_context_at(&bob(), 2 * DAY_NS);
+    contract.invalidate_compromised_resolutions(
+        tee_signing_key().verifying_key().to_bytes().to_vec(),
+        10,
+    )
+}
+
+#[test]
+fn test_revoking_compromised_key_resets_resolutions() {
+    let mut contract = create_test_contract();
+    let operator: near_sdk::AccountId = "phala-agent.testnet".parse().unwrap();
+    register_test_tee_key(&mut contract, &operator);
+    set_fee_via_governance(&mut contract, 50_000_000_000_000_000_000_000);
+    let id = raise_test_dispute(&mut contract);
+    setup_context_at(&operator, DAY_NS);
+    submit_signed_resolution(&mut contract, &id, Resolution::Freelancer, "Resolution: Freelancer");
+
+    revoke_test_tee_key(&mut contract, DAY_NS);
+    assert!(contract.get_tee_registry().is_empty());
+    assert_eq!(contract.get_tee_revocations().len(), 1);
+
+    assert_eq!(invalidate_revoked_key(&mut contract), 0);
+
+    let escrow = contract.get_contract(id.clone()).unwrap();
+    assert_eq!(escrow.disputes[0].status, DisputeStatus::Pending);
+    assert!(escrow.disputes[0].resolution.is_none());
+    assert!(!escrow.disputes[0].ai_fee_deducted);
+    assert_eq!(escrow.security_pool, NearToken::from_near(1));
+    assert_eq!(contract.get_operator_earnings(operator), NearToken::from_yoctonear(0));
+    assert!(contract.get_tee_revocations().is_empty());
+    assert_eq!(contract.get_pending_disputes(), vec![(id, "m1".to_string())]);
+}
+
+#[test]
+#[should_panic(expected = "Resolution was signed by a revoked key and awaits re-arbitration")]
+fn test_revoked_resolution_cannot_be_released_before_reset() {
+    let mut contract = create_test_contract();
+    let operator: near_sdk::AccountId = "phala-agent.testnet".parse().unwrap();
+    register_test_tee_key(&mut contract, &operator);
+    let id = raise_test_dispute(&mut contract);
+    setup_context_at(&operator, DAY_NS);
+    submit_signed_resolution(&mut contract, &id, Resolution::Freelancer, "Resolution: Freelancer");
+
+    revoke_test_tee_key(&mut contract, DAY_NS);
+    setup_context_at(&bob(), 5 * DAY_NS);
+    contract.finalize_resolution(id.clone(), "m1".into());
+    contract.release_dispute_funds(id, "m1".into());
+}
+
+#[test]
+fn test_revoking_compromised_key_resets_appealed_dispute() {
+    let mut contract = create_test_contract();
+    let jurors: Vec<near_sdk::AccountId> = ["j1.testnet", "j2.testnet", "j3.testnet"]
+        .iter()
+        .map(|a| a.parse().unwrap())
+        .collect();
+    for juror in &jurors {
+        setup_context(juror, 5_000_000_000_000_000_000_000_000);
+        contract.stake_as_juror();
+    }
+    let operator: near_sdk::AccountId = "phala-agent.testnet".parse().unwrap();
+    register_test_tee_key(&mut contract, &operator);
+    let id = raise_test_dispute(&mut contract);
+    setup_context_at(&operator, DAY_NS);
+    submit_signed_resolution(&mut contract, &id, Resolution::Freelancer, "Resolution: Freelancer");
+
+    let context = VMContextBuilder::new()
+        .predecessor_account_id(alice())
+        .attached_deposit(NearToken::from_near(1))
+        .block_timestamp(DAY_NS)
+        .build();
+    testing_env!(context);
+    contract.appeal_to_jury(id.clone(), "m1".into());
+
+    revoke_test_tee_key(&mut contract, DAY_NS);
+    assert_eq!(invalidate_revoked_key(&mut contract), 0);
+
+    let dispute = contract.get_dispute(id, "m1".into()).unwrap();
+    assert_eq!(dispute.status, DisputeStatus::Pending);
+    assert!(dispute.jury.is_none());
+    for juror in &jurors {
+        assert_eq!(contract.get_juror(juror.clone()).unwrap().active_cases, 0);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Action is still time-locked")]
+fn test_governance_action_is_time_locked() {
+    let mut contract = create_test_contract();
+
+    setup_context(&owner(), 0);
+    let action_id = contract.set_ai_processing_fee(U128(1));
+    setup_context_at(&owner(), DAY_NS - 1);
+    contract.execute_action(action_id);
+}
+
+#[test]
+fn test_two_step_ownership_transfer() {
+    let mut contract = create_test_contract();
+
+    setup_context(&owner(), 0);
+    contract.transfer_ownership(bob());
+    assert_eq!(contract.get_owner(), &owner());
+    assert_eq!(contract.get_pending_owner(), Some(bob()));
+
+    setup_context(&bob(), 0);
+    contract.accept_ownership();
+    assert_eq!(contract.get_owner(), &bob());
+    assert!(contract.get_pending_owner().is_none());
+}
+
+#[test]
+fn test_tee_registry_manager_role() {
+    let mut contract = create_test_contract();
+    let ops: near_sdk::AccountId = "ops.testnet".parse().unwrap();
+
+    setup_context(&owner(), 0);
+    contract.grant_role(Role::TeeRegistryManager, ops.clone());
+    assert_eq!(contract.get_role_members(Role::TeeRegistryManager), vec![ops.clone()]);
+    assert!(!contract.has_role(Role::Upgrader, ops.clone()));
+
+    setup_context(&ops, 0);
+    let action_id = contract.register_tee_address(
+        vec![7u8; 32],
+        "attestation-sha256".into(),
+        vec!["model".into()],
+        None,
+        None,
+        ops.clone(),
+    );
+    setup_context_at(&ops, DAY_NS);
+    contract.execute_action(action_id);
+    assert_eq!(contract.get_tee_registry().len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Missing role: FeeManager")]
+fn test_fee_change_requires_fee_manager() {
+    let mut contract = create_test_contract();
+    let ops: near_sdk::AccountId = "ops.testnet".parse().unwrap();
+
+    setup_context(&owner(), 0);
+    contract.grant_role(Role::TeeRegistryManager, ops.clone());
+
+    setup_context(&ops, 0);
+    contract.set_ai_processing_fee(U128(1));
+}
+
+#[test]
+#[should_panic(expected = "Paused: Releases")]
+fn test_paused_releases_block_approval() {
+    let mut contract = create_test_contract();
+    let id = create_escrow_with_milestone(&mut contract);
+
+    setup_context(&alice(), 11_000_000_000_000_000_000_000_000);
+    contract.fund_contract(id.clone());
+    setup_context(&bob(), 0);
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
+
+    setup_context(&owner(), 0);
+    contract.pause(PauseScope::Releases);
+    assert_eq!(contract.get_paused_scopes(), &[PauseScope::Releases]);
+
+    setup_context(&alice(), 0);
+    contract.approve_milestone(id, "m1".into());
+}
+
+#[test]
+fn test_refund_release_allowed_while_paused() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+    resolve_test_dispute(&mut contract, &id, Resolution::Client);
+
+    setup_context(&owner(), 0);
+    contract.pause(PauseScope::Releases);
+
+    setup_context_at(&alice(), 3 * DAY_NS);
+    contract.finalize_resolution(id.clone(), "m1".into());
+    contract.release_dispute_funds(id.clone(), "m1".into());
+    assert_eq!(contract.get_contract(id).unwrap().status, ContractStatus::Resolved);
+}
+
+#[test]
+fn test_ai_fee_snapshotted_at_creation() {
+    let mut contract = create_test_contract();
+    set_fee_via_governance(&mut contract, 50_000_000_000_000_000_000_000);
+    let id = create_escrow_with_milestone(&mut contract);
+
+    set_fee_via_governance(&mut contract, 500_000_000_000_000_000_000_000);
+
+    assert_eq!(contract.get_ai_processing_fee(), NearToken::from_millinear(500));
+    assert_eq!(contract.get_contract(id).unwrap().ai_fee, NearToken::from_millinear(50));
+}
+
+#[test]
+#[should_panic(expected = "AI processing fee exceeds the protocol maximum")]
+fn test_ai_fee_capped() {
+    let mut contract = create_test_contract();
+    setup_context(&owner(), 0);
+    contract.set_ai_processing_fee(U128(2_000_000_000_000_000_000_000_000));
+}
+
+#[test]
+fn test_platform_fee_accrues_to_treasury() {
+    let mut contract = create_test_contract();
+
+    setup_context(&owner(), 0);
+    let action_id = contract.set_platform_fee(250);
+    setup_context_at(&owner(), DAY_NS);
+    contract.execute_action(action_id);
+
+    let id = create_escrow_with_milestone(&mut contract);
+    assert_eq!(contract.get_contract(id.clone()).unwrap().platform_fee_bps, 250);
+
+    setup_context(&alice(), 11_000_000_000_000_000_000_000_000);
+    contract.fund_contract(id.clone());
+    setup_context(&bob(), 0);
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
+    setup_context(&alice(), 0);
+    contract.approve_milestone(id, "m1".into());
+
+    // 2.5% of the 10 NEAR milestone
+    assert_eq!(contract.get_treasury_balance(), NearToken::from_millinear(250));
+
+    setup_context(&owner(), 0);
+    contract.withdraw_treasury(None);
+    assert_eq!(contract.get_treasury_balance(), NearToken::from_yoctonear(0));
+}
+
+#[test]
+#[should_panic(expected = "Paused: Releases")]
+fn test_treasury_withdrawal_blocked_while_releases_paused() {
+    let mut contract = create_test_contract();
+    setup_context(&owner(), 0);
+    contract.pause(PauseScope::Releases);
+    contract.withdraw_treasury(None);
+}
+
+#[test]
+fn test_ai_fee_split_between_operator_and_treasury() {
+    let mut contract = create_test_contract();
+    let operator: near_sdk::AccountId = "phala-agent.testnet".parse().unwrap();
+    register_test_tee_key(&mut contract, &operator);
+    set_fee_via_governance(&mut contract, 100_000_000_000_000_000_000_000);
+
+    setup_context(&owner(), 0);
+    let action_id = contract.set_ai_fee_operator_share(7_000);
+    setup_context_at(&owner(), DAY_NS);
+    contract.execute_action(action_id);
+
+    let id = raise_test_dispute(&mut contract);
+    setup_context_at(&operator, DAY_NS);
+    submit_signed_resolution(&mut contract, &id, Resolution::Freelancer, "Resolution: Freelancer");
+
+    // The fee is held until the ruling is final and its funds are released.
+    assert_eq!(contract.get_operator_earnings(operator.clone()), NearToken::from_yoctonear(0));
+    assert_eq!(contract.get_treasury_balance(), NearToken::from_yoctonear(0));
+    let escrow = contract.get_contract(id.clone()).unwrap();
+    assert_eq!(escrow.disputes[0].status, DisputeStatus::AiResolved);
+    assert_eq!(escrow.security_pool, NearToken::from_millinear(900));
+
+    setup_context_at(&bob(), 5 * DAY_NS);
+    contract.finalize_resolution(id.clone(), "m1".into());
+    contract.release_dispute_funds(id.clone(), "m1".into());
+    assert_eq!(contract.get_operator_earnings(operator), NearToken::from_millinear(70));
+    assert_eq!(contract.get_treasury_balance(), NearToken::from_millinear(30));
+    let dispute = contract.get_dispute(id, "m1".into()).unwrap();
+    assert_eq!(dispute.ai_fee_operator_share, NearToken::from_millinear(70));
+}
+
+#[test]
+fn test_revoked_key_returns_held_fee_without_deposit() {
+    let mut contract = create_test_contract();
+    let operator: near_sdk::AccountId = "phala-agent.testnet".parse().unwrap();
+    register_test_tee_key(&mut contract, &operator);
+    set_fee_via_governance(&mut contract, 100_000_000_000_000_000_000_000);
+
+    setup_context(&owner(), 0);
+    let action_id = contract.set_ai_fee_operator_share(7_000);
+    setup_context_at(&owner(), DAY_NS);
+    contract.execute_action(action_id);
+
+    let id = raise_test_dispute(&mut contract);
+    setup_context_at(&operator, DAY_NS);
+    submit_signed_resolution(&mut contract, &id, Resolution::Freelancer, "Resolution: Freelancer");
+
+    revoke_test_tee_key(&mut contract, DAY_NS);
+    assert_eq!(invalidate_revoked_key(&mut contract), 0);
+
+    assert_eq!(contract.get_treasury_balance(), NearToken::from_yoctonear(0));
+    assert_eq!(contract.get_operator_earnings(operator), NearToken::from_yoctonear(0));
+    let escrow = contract.get_contract(id).unwrap();
+    assert_eq!(escrow.disputes[0].status, DisputeStatus::Pending);
+    assert_eq!(escrow.security_pool, NearToken::from_near(1));
+}
+
+#[test]
+fn test_claimed_dispute_hidden_until_lease_expires() {
+    let mut contract = create_test_contract();
+    let operator: near_sdk::AccountId = "phala-agent.testnet".parse().unwrap();
+    register_test_tee_key(&mut contract, &operator);
+    let id = raise_test_dispute(&mut contract);
+
+    setup_context_at(&operator, DAY_NS);
+    contract.claim_dispute(
+        id.clone(),
+        "m1".into(),
+        tee_signing_key().verifying_key().to_bytes().to_vec(),
+        None,
+    );
+    assert!(contract.get_pending_disputes().is_empty());
+
+    setup_context_at(&operator, DAY_NS + 10 * 60 * 1_000_000_000);
+    assert_eq!(contract.get_pending_disputes(), vec![(id, "m1".to_string())]);
+}
+
+#[test]
+#[should_panic(expected = "Only the key's operator can claim with it")]
+fn test_claim_requires_key_operator() {
+    let mut contract = create_test_contract();
+    let operator: near_sdk::AccountId = "phala-agent.testnet".parse().unwrap();
+    register_test_tee_key(&mut contract, &operator);
+    let id = raise_test_dispute(&mut contract);
+
+    setup_context_at(&bob(), DAY_NS);
+    contract.claim_dispute(id, "m1".into(), tee_signing_key().verifying_key().to_bytes().to_vec(), None);
+}
+
+#[test]
+fn test_ai_resolution_accepts_gateway_payload_with_evidence() {
+    let mut contract = create_test_contract();
+    let operator: near_sdk::AccountId = "phala-agent.testnet".parse().unwrap();
+    register_test_tee_key(&mut contract, &operator);
+    let id = raise_test_dispute(&mut contract);
+
+    setup_context(&alice(), 0);
+    contract.submit_evidence(id.clone(), "m1".into(), vec![1; 32], "nova://evidence/1".into());
+    setup_context(&bob(), 0);
+    contract.submit_evidence(id.clone(), "m1".into(), vec![2; 32], "ipfs://bafy".into());
+
+    let root = contract.get_evidence_root(id.clone(), "m1".into()).unwrap();
+    assert_eq!(root.len(), 64);
+
+    setup_context_at(&operator, DAY_NS);
+    submit_signed_resolution(&mut contract, &id, Resolution::Client, "Resolution: Client");
+    let dispute = contract.get_dispute(id, "m1".into()).unwrap();
+    assert_eq!(dispute.status, DisputeStatus::AiResolved);
+}
+
+#[test]
+#[should_panic(expected = "Evidence submission window has closed")]
+fn test_evidence_frozen_once_response_window_waived() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+
+    setup_context(&alice(), 0);
+    contract.waive_response_window(id.clone(), "m1".into());
+    setup_context(&bob(), 0);
+    contract.waive_response_window(id.clone(), "m1".into());
+
+    setup_context(&alice(), 0);
+    contract.submit_evidence(id, "m1".into(), vec![1; 32], "nova://evidence/1".into());
+}
+
+#[test]
+#[should_panic(expected = "Evidence submission window has closed")]
+fn test_evidence_rejected_after_deadline() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+
+    setup_context_at(&bob(), DAY_NS + 1);
+    contract.submit_evidence(id, "m1".into(), vec![2; 32], "ipfs://bafy".into());
+}
+
+#[test]
+fn test_statements_hold_dispute_until_both_parties_waive() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+
+    setup_context(&bob(), 0);
+    contract.file_statement(id.clone(), "m1".into(), Some("All deliverables were sent".into()), None);
+    contract.waive_response_window(id.clone(), "m1".into());
+    assert!(contract.get_pending_disputes().is_empty());
+
+    setup_context(&alice(), 0);
+    contract.file_statement(id.clone(), "m1".into(), None, Some(vec![7; 32]));
+    contract.waive_response_window(id.clone(), "m1".into());
+    assert_eq!(contract.get_pending_disputes(), vec![(id.clone(), "m1".to_string())]);
+
+    let dispute = contract.get_dispute(id, "m1".into()).unwrap();
+    assert_eq!(dispute.statements.len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "Response window has closed")]
+fn test_statement_rejected_after_response_window() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+
+    setup_context_at(&bob(), DAY_NS);
+    contract.file_statement(id, "m1".into(), Some("Too late".into()), None);
+}
+
+#[test]
+fn test_dispute_context_exposes_category_and_claim() {
+    let mut contract = create_test_contract();
+    let id = create_escrow_with_milestone(&mut contract);
+
+    setup_context(&alice(), 11_000_000_000_000_000_000_000_000);
+    contract.fund_contract(id.clone());
+    setup_context(&bob(), 0);
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
+    setup_context(&alice(), 0);
+    contract.raise_dispute(
+        id.clone(),
+        "m1".into(),
+        "Missing the mobile layout".into(),
+        DisputeCategory::Quality,
+        ClaimedRemedy::PartialRefund { amount: U128(3_000_000_000_000_000_000_000_000) },
+    );
+
+    let context = contract.get_dispute_context(id, "m1".into()).unwrap();
+    assert_eq!(context.category, DisputeCategory::Quality);
+    assert_eq!(context.amount, NearToken::from_near(10));
+    assert_eq!(
+        context.claimed_remedy,
+        ClaimedRemedy::PartialRefund { amount: U128(3_000_000_000_000_000_000_000_000) }
+    );
+}
+
+#[test]
+#[should_panic(expected = "Resolution exceeds the remedy claimed by the client")]
+fn test_ruling_cannot_exceed_claimed_remedy() {
+    let mut contract = create_test_contract();
+    let operator: near_sdk::AccountId = "phala-agent.testnet".parse().unwrap();
+    register_test_tee_key(&mut contract, &operator);
+    let id = create_escrow_with_milestone(&mut contract);
+
+    setup_context(&alice(), 11_000_000_000_000_000_000_000_000);
+    contract.fund_contract(id.clone());
+    setup_context(&bob(), 0);
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
+    setup_context(&alice(), 0);
+    contract.raise_dispute(
+        id.clone(),
+        "m1".into(),
+        "Needs fixes".into(),
+        DisputeCategory::Quality,
+        ClaimedRemedy::Rework,
+    );
+
+    setup_context_at(&operator, DAY_NS);
+    let split = Resolution::Split { freelancer_pct: 50 };
+    submit_signed_resolution(&mut contract, &id, split, "Resolution: Split 50");
+}
+
+#[test]
+fn test_payment_requests_keep_deliverable_history() {
+    let mut contract = create_test_contract();
+    let id = create_escrow_with_milestone(&mut contract);
+
+    setup_context(&alice(), 11_000_000_000_000_000_000_000_000);
+    contract.fund_contract(id.clone());
+
+    setup_context(&bob(), 0);
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
+    contract.cancel_payment_request(id.clone(), "m1".into());
+    contract.request_payment(
+        id.clone(),
+        "m1".into(),
+        vec![2; 32],
+        "ipfs://v2".into(),
+        Some("Fixed the footer".into()),
+    );
+
+    let escrow = contract.get_contract(id.clone()).unwrap();
+    let versions: Vec<u32> = escrow.milestones[0].deliverables.iter().map(|d| d.version).collect();
+    assert_eq!(versions, vec![1, 2]);
+
+    setup_context(&alice(), 0);
+    contract.raise_dispute(
+        id.clone(),
+        "m1".into(),
+        "Footer still broken".into(),
+        DisputeCategory::Quality,
+        ClaimedRemedy::FullRefund,
+    );
+    let context = contract.get_dispute_context(id, "m1".into()).unwrap();
+    assert_eq!(context.deliverable.unwrap().uri, "ipfs://v2");
+}
+
+#[test]
+#[should_panic(expected = "Revision limit reached; approve or dispute the milestone")]
+fn test_request_changes_is_capped() {
+    let mut contract = create_test_contract();
+    let id = create_escrow_with_milestone(&mut contract);
+
+    setup_context(&alice(), 11_000_000_000_000_000_000_000_000);
+    contract.fund_contract(id.clone());
+
+    for _ in 0..3 {
+        setup_context(&bob(), 0);
+        contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
+        setup_context(&alice(), 0);
+        contract.request_changes(id.clone(), "m1".into(), vec![9; 32]);
+
+        let escrow = contract.get_contract(id.clone()).unwrap();
+        assert_eq!(escrow.milestones[0].status, MilestoneStatus::InProgress);
+    }
+}
+
+fn create_overdue_milestone(contract: &mut Contract) -> String {
+    let id = create_escrow_with_milestone(contract);
+    let mut escrow = contract.get_contract(id.clone()).unwrap();
+    escrow.milestones[0].due_date_ns = Some(DAY_NS);
+    contract.contracts.insert(id.clone(), escrow);
+
+    setup_context(&alice(), 11_000_000_000_000_000_000_000_000);
+    contract.fund_contract(id.clone());
+    id
+}
+
+#[test]
+fn test_reclaim_overdue_milestone() {
+    let mut contract = create_test_contract();
+    let id = create_overdue_milestone(&mut contract);
+
+    setup_context_at(&alice(), 2 * DAY_NS);
+    assert!(contract.get_overdue_milestones(id.clone()).is_empty());
+
+    setup_context_at(&alice(), 4 * DAY_NS);
+    assert_eq!(contract.get_overdue_milestones(id.clone()).len(), 1);
+    contract.reclaim_overdue_milestone(id.clone(), "m1".into());
+
+    let escrow = contract.get_contract(id.clone()).unwrap();
+    assert_eq!(escrow.milestones[0].status, MilestoneStatus::Refunded);
+    assert_eq!(escrow.status, ContractStatus::Completed);
+    assert!(contract.get_overdue_milestones(id).is_empty());
+}
+
+#[test]
+fn test_security_refund_to_client_allowed_while_paused() {
+    let mut contract = create_test_contract();
+    let id = create_overdue_milestone(&mut contract);
+
+    setup_context_at(&alice(), 4 * DAY_NS);
+    contract.reclaim_overdue_milestone(id.clone(), "m1".into());
+
+    setup_context(&owner(), 0);
+    contract.pause(PauseScope::Releases);
+
+    setup_context_at(&alice(), 4 * DAY_NS);
+    contract.complete_contract_security(id.clone());
+    assert_eq!(contract.get_contract(id).unwrap().security_pool.as_yoctonear(), 0);
+}
+
+#[test]
+fn test_non_delivery_dispute_on_overdue_milestone() {
+    let mut contract = create_test_contract();
+    let id = create_overdue_milestone(&mut contract);
+
+    setup_context_at(&alice(), 4 * DAY_NS);
+    contract.raise_dispute(
+        id.clone(),
+        "m1".into(),
+        "Freelancer stopped responding".into(),
+        DisputeCategory::NonDelivery,
+        ClaimedRemedy::FullRefund,
+    );
+    contract.withdraw_dispute(id.clone(), "m1".into(), false);
+
+    let escrow = contract.get_contract(id).unwrap();
+    assert_eq!(escrow.milestones[0].status, MilestoneStatus::InProgress);
+}
+
+#[test]
+fn test_accepted_extension_delays_auto_approval() {
+    let mut contract = create_test_contract();
+    let id = create_escrow_with_milestone(&mut contract);
+
+    setup_context(&alice(), 11_000_000_000_000_000_000_000_000);
+    contract.fund_contract(id.clone());
+    setup_context(&bob(), 0);
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
+
+    setup_context(&alice(), 0);
+    contract.request_extension(id.clone(), "m1".into(), DeadlineKind::PaymentReview, U64(DAY_NS));
+    setup_context(&bob(), 0);
+    contract.respond_to_extension(id.clone(), "m1".into(), true);
+
+    let escrow = contract.get_contract(id.clone()).unwrap();
+    assert_eq!(escrow.milestones[0].payment_request_deadline_ns, Some(3 * DAY_NS));
+    assert_eq!(escrow.milestones[0].extensions[0].status, ExtensionStatus::Accepted);
+
+    setup_context_at(&bob(), 3 * DAY_NS);
+    contract.auto_approve_payment(id.clone(), "m1".into());
+    let escrow = contract.get_contract(id).unwrap();
+    assert_eq!(escrow.milestones[0].status, MilestoneStatus::Completed);
+}
+
+#[test]
+#[should_panic(expected = "Only the counterparty can respond to an extension request")]
+fn test_requester_cannot_accept_own_extension() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+    resolve_test_dispute(&mut contract, &id, Resolution::Client);
+
+    setup_context(&bob(), 0);
+    contract.request_extension(id.clone(), "m1".into(), DeadlineKind::DisputeReview, U64(DAY_NS));
+    contract.respond_to_extension(id, "m1".into(), true);
+}
+
+#[test]
+fn test_custom_time_windows_drive_review_deadline() {
+    let mut contract = create_test_contract();
+    let windows = TimeWindows {
+        payment_review_ns: 12 * 60 * 60 * 1_000_000_000,
+        dispute_review_ns: DAY_NS,
+        payment_cooldown_ns: 60 * 60 * 1_000_000_000,
+    };
+    setup_context(&alice(), 50_000_000_000_000_000_000_000);
+    let id = contract.create_contract(
+        "Gig".into(),
+        "Logo".into(),
+        vec![MilestoneInput {
+            title: "M1".into(),
+            description: "D1".into(),
+            amount: U128(1_000_000_000_000_000_000_000_000),
+            revision_limit: 2,
+            due_date_ns: None,
+        }],
+        Some(bob()),
+        10,
+        "hash".into(),
+        "model".into(),
+        None,
+        None,
+        Some(windows.clone()),
+    );
+
+    setup_context(&alice(), 1_100_000_000_000_000_000_000_000);
+    contract.fund_contract(id.clone());
+    setup_context(&bob(), 0);
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
+
+    let escrow = contract.get_contract(id).unwrap();
+    assert_eq!(escrow.time_windows, windows);
+    assert_eq!(escrow.milestones[0].payment_request_deadline_ns, Some(windows.payment_review_ns));
+}
+
+#[test]
+#[should_panic(expected = "Time windows are outside the allowed bounds")]
+fn test_time_windows_respect_owner_bounds() {
+    let mut contract = create_test_contract();
+    setup_context(&alice(), 50_000_000_000_000_000_000_000);
+    contract.create_contract(
+        "Gig".into(),
+        "Logo".into(),
+        vec![MilestoneInput {
+            title: "M1".into(),
+            description: "D1".into(),
+            amount: U128(1_000_000_000_000_000_000_000_000),
+            revision_limit: 2,
+            due_date_ns: None,
+        }],
+        Some(bob()),
+        10,
+        "hash".into(),
+        "model".into(),
+        None,
+        None,
+        Some(TimeWindows {
+            payment_review_ns: 60 * 1_000_000_000,
+            dispute_review_ns: DAY_NS,
+            payment_cooldown_ns: 0,
+        }),
+    );
+}
+
+#[test]
+fn test_hold_freezes_and_shifts_review_deadline() {
+    let mut contract = create_test_contract();
+    let id = create_escrow_with_milestone(&mut contract);
+
+    setup_context(&alice(), 11_000_000_000_000_000_000_000_000);
+    contract.fund_contract(id.clone());
+    setup_context(&bob(), 0);
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
+
+    setup_context_at(&alice(), DAY_NS);
+    contract.propose_hold(id.clone());
+    setup_context_at(&bob(), DAY_NS);
+    contract.accept_hold(id.clone(), true);
+    assert_eq!(contract.get_contract(id.clone()).unwrap().status, ContractStatus::OnHold);
+
+    setup_context_at(&alice(), 11 * DAY_NS);
+    contract.resume_contract(id.clone());
+
+    let escrow = contract.get_contract(id).unwrap();
+    assert_eq!(escrow.status, ContractStatus::Active);
+    assert_eq!(escrow.milestones[0].payment_request_deadline_ns, Some(12 * DAY_NS));
+}
+
+#[test]
+#[should_panic(expected = "Contract is on hold or cancelled")]
+fn test_auto_approve_blocked_while_on_hold() {
+    let mut contract = create_test_contract();
+    let id = create_escrow_with_milestone(&mut contract);
+
+    setup_context(&alice(), 11_000_000_000_000_000_000_000_000);
+    contract.fund_contract(id.clone());
+    setup_context(&bob(), 0);
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
+    contract.propose_hold(id.clone());
+    setup_context(&alice(), 0);
+    contract.accept_hold(id.clone(), true);
+
+    setup_context_at(&bob(), 3 * DAY_NS);
+    contract.auto_approve_payment(id, "m1".into());
+}
+
+#[test]
+fn test_cancel_held_contract_needs_both_parties() {
+    let mut contract = create_test_contract();
+    let id = create_escrow_with_milestone(&mut contract);
+
+    setup_context(&alice(), 11_000_000_000_000_000_000_000_000);
+    contract.fund_contract(id.clone());
+    contract.propose_hold(id.clone());
+    setup_context(&bob(), 0);
+    contract.accept_hold(id.clone(), true);
+
+    contract.cancel_held_contract(id.clone());
+    assert_eq!(contract.get_contract(id.clone()).unwrap().status, ContractStatus::OnHold);
+
+    setup_context(&alice(), 0);
+    contract.cancel_held_contract(id.clone());
+    let escrow = contract.get_contract(id).unwrap();
+    assert_eq!(escrow.status, ContractStatus::Cancelled);
+    assert_eq!(escrow.milestones[0].status, MilestoneStatus::Refunded);
+    assert_eq!(escrow.security_pool, NearToken::from_yoctonear(0));
+}
+
+fn redispute_after_cooldown(contract: &mut Contract, id: &str, at_ns: u64) {
+    setup_context_at(&bob(), at_ns);
+    contract.request_payment(id.into(), "m1".into(), vec![1; 32], "ipfs://v2".into(), None);
+    setup_context_at(&alice(), at_ns);
+    contract.raise_dispute(
+        id.into(),
+        "m1".into(),
+        "Still incomplete".into(),
+        DisputeCategory::Quality,
+        ClaimedRemedy::FullRefund,
+    );
+    resolve_test_dispute(contract, id, Resolution::Client);
+}
+
+#[test]
+#[should_panic(expected = "Override limit reached; the resolution stands")]
+fn test_override_is_capped_with_rising_cooldown() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+    resolve_test_dispute(&mut contract, &id, Resolution::Client);
+    setup_context(&alice(), 0);
+    contract.override_to_continue_work(id.clone(), "m1".into());
+
+    redispute_after_cooldown(&mut contract, &id, DAY_NS);
+    contract.override_to_continue_work(id.clone(), "m1".into());
+    let escrow = contract.get_contract(id.clone()).unwrap();
+    assert_eq!(escrow.milestones[0].payment_request_blocked_until_ns, Some(3 * DAY_NS));
+
+    redispute_after_cooldown(&mut contract, &id, 3 * DAY_NS);
+    contract.override_to_continue_work(id, "m1".into());
+}
+
+#[test]
+fn test_release_funds_after_override() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+    resolve_test_dispute(&mut contract, &id, Resolution::Client);
+    setup_context(&alice(), 0);
+    contract.override_to_continue_work(id.clone(), "m1".into());
+
+    redispute_after_cooldown(&mut contract, &id, DAY_NS);
+    setup_context_at(&alice(), 3 * DAY_NS);
+    contract.finalize_resolution(id.clone(), "m1".into());
+    contract.release_dispute_funds(id.clone(), "m1".into());
+
+    let escrow = contract.get_contract(id).unwrap();
+    assert!(escrow.disputes.iter().all(|d| d.funds_released));
+    assert_eq!(escrow.status, ContractStatus::Resolved);
+}
+
+#[test]
+fn test_override_cooldown_applies_without_escrow_cooldown() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+    let mut escrow = contract.get_contract(id.clone()).unwrap();
+    escrow.time_windows.payment_cooldown_ns = 0;
+    contract.contracts.insert(id.clone(), escrow);
+    resolve_test_dispute(&mut contract, &id, Resolution::Client);
+
+    setup_context(&alice(), 0);
+    contract.override_to_continue_work(id.clone(), "m1".into());
+    let escrow = contract.get_contract(id).unwrap();
+    assert_eq!(escrow.milestones[0].payment_request_blocked_until_ns, Some(DAY_NS));
+}
+
+#[test]
+fn test_override_uses_configured_cooldown() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+    let mut escrow = contract.get_contract(id.clone()).unwrap();
+    escrow.time_windows.payment_cooldown_ns = 2 * 60 * 60 * 1_000_000_000;
+    contract.contracts.insert(id.clone(), escrow);
+    resolve_test_dispute(&mut contract, &id, Resolution::Client);
+
+    setup_context(&alice(), 0);
+    contract.override_to_continue_work(id.clone(), "m1".into());
+    let escrow = contract.get_contract(id).unwrap();
+    assert_eq!(
+        escrow.milestones[0].payment_request_blocked_until_ns,
+        Some(2 * 60 * 60 * 1_000_000_000)
+    );
+}
+
+#[test]
+fn test_override_of_accepted_split_compensates_freelancer() {
+    let mut contract = create_test_contract();
+    let id = raise_test_dispute(&mut contract);
+    resolve_test_dispute(&mut contract, &id, Resolution::Split { freelancer_pct: 60 });
+
+    setup_context(&bob(), 0);
+    contract.accept_resolution(id.clone(), "m1".into());
+    setup_context(&alice(), 0);
+    contract.override_to_continue_work(id.clone(), "m1".into());
+
+    // 10% of the freelancer's 6 NEAR share comes out of the 1 NEAR pool.
+    let escrow = contract.get_contract(id).unwrap();
+    assert_eq!(escrow.security_pool, NearToken::from_millinear(400));
+    assert_eq!(escrow.milestones[0].overrides_used, 1);
+}
+
+#[test]
+#[should_panic(expected = "Milestone is not overdue")]
+fn test_on_time_delivery_sent_back_is_not_reclaimable() {
+    let mut contract = create_test_contract();
+    let id = create_overdue_milestone(&mut contract);
+
+    setup_context_at(&bob(), DAY_NS / 2);
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
+
+    setup_context_at(&alice(), 5 * DAY_NS);
+    contract.request_changes(id.clone(), "m1".into(), vec![9; 32]);
+    assert!(contract.get_overdue_milestones(id.clone()).is_empty());
+    contract.reclaim_overdue_milestone(id, "m1".into());
+}
+
+#[test]
+#[should_panic(expected = "Governance delay must be between 1 hour and 30 days")]
+fn test_governance_delay_is_bounded() {
+    let mut contract = create_test_contract();
+    setup_context(&owner(), 0);
+    contract.set_governance_delay(U64(u64::MAX));
+}
+
+#[test]
+#[should_panic(expected = "Action is still time-locked")]
+fn test_upgrade_waits_for_time_lock() {
+    let mut contract = create_test_contract();
+    let code = b"new wasm".to_vec();
+    setup_context(&owner(), 0);
+    contract.propose_update(near_sdk::env::sha256(&code));
+
+    let mut context = VMContextBuilder::new()
+        .predecessor_account_id(owner())
+        .block_timestamp(DAY_NS - 1)
+        .build();
+    context.input = code;
+    testing_env!(context);
+    contract.update_contract();
+}
+
+#[test]
+fn test_migrate_converts_original_layout() {
+    use crate::migration::{ContractV0, EscrowContractV0, MilestoneV0};
+    use near_sdk::store::IterableMap;
+
+    setup_context(&owner(), 0);
+    let mut old = ContractV0 {
+        contracts: IterableMap::new(b"c"),
+        account_contracts: IterableMap::new(b"a"),
+        trusted_tee_addresses: vec![vec![7u8; 32]],
+        owner: owner(),
+        next_id: 1,
+        ai_processing_fee: NearToken::from_millinear(100),
+    };
+    old.contracts.insert(
+        "c1".into(),
+        EscrowContractV0 {
+            id: "c1".into(),
+            title: "Legacy".into(),
+            description: "Description".into(),
+            client: alice(),
+            freelancer: Some(bob()),
+            total_amount: NearToken::from_near(10),
+            funded_amount: NearToken::from_near(10),
+            security_deposit_pct: 10,
+            milestones: vec![MilestoneV0 {
+                id: "m1".into(),
+                title: "Milestone 1".into(),
+                description: "Build feature".into(),
+                amount: NearToken::from_near(10),
+                status: MilestoneStatus::InProgress,
+                payment_request_deadline_ns: None,
+                payment_request_blocked_until_ns: None,
+            }],
+            status: ContractStatus::Active,
+            created_at: 0,
+            invite_token: None,
+            prompt_hash: "abc123hash".into(),
+            disputes: vec![],
+            model_id: "Qwen/Qwen3-30B-A3B-Instruct-2507".into(),
+            security_pool: NearToken::from_near(1),
+        },
+    );
+    old.account_contracts.insert(alice(), vec!["c1".into()]);
+    old.contracts.flush();
+    old.account_contracts.flush();
+    near_sdk::env::state_write(&old);
+
+    setup_context(&"contract.testnet".parse().unwrap(), 0);
+    let mut contract = Contract::migrate();
+    assert_eq!(contract.get_state_version(), crate::migration::STATE_VERSION);
+    assert_eq!(contract.tee_keys.len(), 1);
+    assert_eq!(contract.get_ai_processing_fee(), NearToken::from_millinear(100));
+    assert!(contract.get_contract("c1".into()).is_none());
+
+    assert_eq!(contract.migrate_escrows(10), 0);
+    assert!(contract.legacy_contracts.is_none());
+    let escrow = contract.get_contract("c1".into()).expect("Escrow was migrated");
+    assert_eq!(escrow.ai_fee, NearToken::from_millinear(100));
+    assert_eq!(escrow.milestones[0].revision_limit, 2);
+    assert_eq!(contract.get_contracts_by_account(alice()).len(), 1);
+
+    setup_context(&alice(), 0);
+    let id = create_escrow_with_milestone(&mut contract);
+    assert_eq!(id, "c2");
+}
+
+fn raise_dispute_with_claim(contract: &mut Contract, claimed_remedy: ClaimedRemedy) -> String {
+    let id = create_escrow_with_milestone(contract);
+    setup_context(&alice(), 11_000_000_000_000_000_000_000_000);
+    contract.fund_contract(id.clone());
+    setup_context(&bob(), 0);
+    contract.request_payment(id.clone(), "m1".into(), vec![1; 32], "ipfs://v1".into(), None);
+    setup_context(&alice(), 0);
+    contract.raise_dispute(
+        id.clone(),
+        "m1".into(),
+        "Missing the mobile layout".into(),
+        DisputeCategory::Quality,
+        claimed_remedy,
+    );
+    id
+}
+
+#[test]
+fn test_fallback_refund_capped_at_partial_claim() {
+    let mut contract = create_test_contract();
+    let id = raise_dispute_with_claim(
+        &mut contract,
+        ClaimedRemedy::PartialRefund { amount: U128(3_000_000_000_000_000_000_000_000) },
+    );
+
+    setup_context_at(&bob(), 8 * DAY_NS);
+    contract.trigger_fallback(id.clone(), "m1".into());
+
+    let dispute = contract.get_dispute(id, "m1".into()).unwrap();
+    assert_eq!(dispute.resolution, Some(Resolution::Split { freelancer_pct: 70 }));
+}
+
+#[test]
+fn test_fallback_on_rework_claim_continues_work() {
+    let mut contract = create_test_contract();
+    let id = raise_dispute_with_claim(&mut contract, ClaimedRemedy::Rework);
+
+    setup_context_at(&bob(), 8 * DAY_NS);
+    contract.trigger_fallback(id.clone(), "m1".into());
+    contract.release_dispute_funds(id.clone(), "m1".into());
+
+    let escrow = contract.get_contract(id).unwrap();
+    assert_eq!(escrow.milestones[0].status, MilestoneStatus::InProgress);
+    assert_eq!(escrow.status, ContractStatus::Active);
+}
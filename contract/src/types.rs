@@ -1,5 +1,6 @@
 use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
 use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::json_types::U128;
 use near_sdk::{AccountId, NearToken};
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Debug)]
@@ -12,6 +13,7 @@ pub enum MilestoneStatus {
     SubmittedForReview,
     Completed,
     Disputed,
+    Refunded,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Debug)]
@@ -23,6 +25,8 @@ pub enum ContractStatus {
     Completed,
     Disputed,
     Resolved,
+    OnHold,
+    Cancelled,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Debug)]
@@ -32,6 +36,42 @@ pub enum DisputeStatus {
     Pending,
     AiResolved,
     Finalized,
+    Settled,
+    Withdrawn,
+    Escalated,
+    Appealed,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum FallbackPolicy {
+    #[default]
+    RefundClient,
+    Split,
+    Arbitrator,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum Arbitrator {
+    #[default]
+    Tee,
+    Human { account_id: AccountId },
+    External { contract_id: AccountId },
+}
+
+impl Arbitrator {
+    /// The account allowed to call `submit_arbitrator_resolution`; TEE rulings
+    /// arrive through `submit_ai_resolution` instead.
+    pub fn account_id(&self) -> Option<&AccountId> {
+        match self {
+            Arbitrator::Tee => None,
+            Arbitrator::Human { account_id } => Some(account_id),
+            Arbitrator::External { contract_id } => Some(contract_id),
+        }
+    }
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Debug)]
@@ -44,6 +84,74 @@ pub enum Resolution {
     Split { freelancer_pct: u8 },
 }
 
+impl Resolution {
+    /// The client gives up some or all of the milestone amount.
+    pub fn client_loses(&self) -> bool {
+        match self {
+            Resolution::Freelancer => true,
+            Resolution::Split { freelancer_pct } => *freelancer_pct > 0,
+            _ => false,
+        }
+    }
+
+    /// Every yoctoNEAR goes back to the client.
+    pub fn is_refund_only(&self) -> bool {
+        matches!(self, Resolution::Client | Resolution::Split { freelancer_pct: 0 })
+    }
+
+    /// The freelancer gives up some or all of the milestone amount.
+    pub fn freelancer_loses(&self) -> bool {
+        match self {
+            Resolution::Client => true,
+            Resolution::Split { freelancer_pct } => *freelancer_pct < 100,
+            _ => false,
+        }
+    }
+
+    /// Portion of `amount` the client gets back under this ruling.
+    pub fn client_share(&self, amount: NearToken) -> NearToken {
+        let refund = match self {
+            Resolution::Client => amount.as_yoctonear(),
+            Resolution::Split { freelancer_pct } => {
+                amount.as_yoctonear() - amount.as_yoctonear() * *freelancer_pct as u128 / 100
+            }
+            Resolution::Freelancer | Resolution::ContinueWork => 0,
+        };
+        NearToken::from_yoctonear(refund)
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum DisputeCategory {
+    NonDelivery,
+    Quality,
+    MissedDeadline,
+    ScopeDisagreement,
+    ClientUnresponsive,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum ClaimedRemedy {
+    FullRefund,
+    PartialRefund { amount: U128 },
+    Rework,
+}
+
+impl ClaimedRemedy {
+    /// The most a ruling may refund to the client without going beyond the claim.
+    pub fn max_refund(&self, milestone_amount: NearToken) -> NearToken {
+        match self {
+            ClaimedRemedy::FullRefund => milestone_amount,
+            ClaimedRemedy::PartialRefund { amount } => NearToken::from_yoctonear(amount.0),
+            ClaimedRemedy::Rework => NearToken::from_yoctonear(0),
+        }
+    }
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
 #[borsh(crate = "near_sdk::borsh")]
 #[serde(crate = "near_sdk::serde")]
@@ -55,6 +163,76 @@ pub struct Milestone {
     pub status: MilestoneStatus,
     pub payment_request_deadline_ns: Option<u64>,
     pub payment_request_blocked_until_ns: Option<u64>,
+    pub deliverables: Vec<Deliverable>,
+    pub revision_limit: u8,
+    pub revision_requests: Vec<RevisionRequest>,
+    pub due_date_ns: Option<u64>,
+    pub extensions: Vec<DeadlineExtension>,
+    pub overrides_used: u8,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum DeadlineKind {
+    DueDate,
+    PaymentReview,
+    DisputeReview,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum ExtensionStatus {
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct DeadlineExtension {
+    pub deadline: DeadlineKind,
+    pub requested_by: AccountId,
+    pub duration_ns: u64,
+    pub requested_at_ns: u64,
+    pub status: ExtensionStatus,
+}
+
+impl Milestone {
+    /// Funded work that is past its due date plus the grace period and was never
+    /// submitted by the due date. Work delivered on time and later sent back for
+    /// changes is not overdue.
+    pub fn is_overdue_at(&self, timestamp_ns: u64, grace_ns: u64) -> bool {
+        let Some(due) = self.due_date_ns else {
+            return false;
+        };
+        matches!(self.status, MilestoneStatus::Funded | MilestoneStatus::InProgress)
+            && timestamp_ns >= due + grace_ns
+            && !self.deliverables.iter().any(|d| d.submitted_at_ns <= due)
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct RevisionRequest {
+    pub feedback_hash: Vec<u8>,
+    pub deliverable_version: u32,
+    pub requested_at_ns: u64,
+}
+
+/// What the freelancer submitted with a payment request; one entry per submission.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct Deliverable {
+    pub version: u32,
+    pub content_hash: Vec<u8>,
+    pub uri: String,
+    pub note: Option<String>,
+    pub submitted_at_ns: u64,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
@@ -64,15 +242,291 @@ pub struct Dispute {
     pub milestone_id: String,
     pub raised_by: AccountId,
     pub reason: String,
+    pub category: DisputeCategory,
+    pub claimed_remedy: ClaimedRemedy,
     pub status: DisputeStatus,
     pub resolution: Option<Resolution>,
     pub explanation: Option<String>,
     pub deadline_ns: Option<u64>,
     pub ai_fee_deducted: bool,
+    pub ai_fee_amount: NearToken,
+    pub ai_fee_operator: Option<AccountId>,
+    pub ai_fee_operator_share: NearToken,
     pub tee_signature: Option<Vec<u8>>,
     pub tee_signing_address: Option<Vec<u8>>,
     pub tee_text: Option<String>,
     pub funds_released: bool,
+    pub client_accepted: bool,
+    pub freelancer_accepted: bool,
+    pub settlement: Option<SettlementProposal>,
+    pub resolution_deadline_ns: Option<u64>,
+    pub escalated_to: Option<AccountId>,
+    pub fallback_applied: bool,
+    pub jury: Option<JuryPanel>,
+    pub resolved_at_ns: Option<u64>,
+    pub lease: Option<DisputeLease>,
+    pub evidence: Vec<EvidenceCommitment>,
+    pub evidence_deadline_ns: Option<u64>,
+    pub statements: Vec<Statement>,
+    pub response_deadline_ns: Option<u64>,
+    pub client_waived_response: bool,
+    pub freelancer_waived_response: bool,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct Statement {
+    pub author: AccountId,
+    pub text: Option<String>,
+    pub content_hash: Option<Vec<u8>>,
+    pub filed_at_ns: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct EvidenceCommitment {
+    pub submitted_by: AccountId,
+    pub content_hash: Vec<u8>,
+    pub locator: String,
+    pub submitted_at_ns: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct DisputeLease {
+    pub agent_key: Vec<u8>,
+    pub operator: AccountId,
+    pub expires_at_ns: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct Juror {
+    pub stake: NearToken,
+    pub active_cases: u32,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct JurorVote {
+    pub juror: AccountId,
+    pub commitment: Option<Vec<u8>>,
+    pub vote: Option<Resolution>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct JuryPanel {
+    pub appellant: AccountId,
+    pub appeal_fee: NearToken,
+    pub votes: Vec<JurorVote>,
+    pub commit_deadline_ns: u64,
+    pub reveal_deadline_ns: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct SettlementProposal {
+    pub proposed_by: AccountId,
+    pub resolution: Resolution,
+    pub proposed_at: u64,
+}
+
+/// A key revoked as compromised whose resolutions are still being reset;
+/// `next_index` is the first escrow not yet scanned.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct TeeRevocation {
+    pub address: Vec<u8>,
+    pub compromised_at_ns: u64,
+    pub next_index: u32,
+}
+
+impl TeeRevocation {
+    pub fn covers(&self, dispute: &Dispute) -> bool {
+        dispute.tee_signing_address.as_ref() == Some(&self.address)
+            && dispute.resolved_at_ns.is_some_and(|t| t >= self.compromised_at_ns)
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct TeeKey {
+    pub public_key: Vec<u8>,
+    pub attestation_hash: String,
+    pub model_ids: Vec<String>,
+    pub valid_from_ns: u64,
+    pub valid_until_ns: Option<u64>,
+    pub operator: AccountId,
+    pub registered_at_ns: u64,
+}
+
+impl TeeKey {
+    pub fn is_valid_at(&self, timestamp_ns: u64) -> bool {
+        timestamp_ns >= self.valid_from_ns
+            && self.valid_until_ns.map(|until| timestamp_ns < until).unwrap_or(true)
+    }
+
+    pub fn allows_model(&self, model_id: &str) -> bool {
+        self.model_ids.iter().any(|m| m == model_id)
+    }
+}
+
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug,
+)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum Role {
+    FeeManager,
+    TeeRegistryManager,
+    Pauser,
+    Upgrader,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum PauseScope {
+    NewContracts,
+    Funding,
+    Releases,
+    AiResolutions,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum GovernanceAction {
+    RegisterTeeKey {
+        address: Vec<u8>,
+        attestation_hash: String,
+        model_ids: Vec<String>,
+        valid_from_ns: Option<u64>,
+        valid_until_ns: Option<u64>,
+        operator: AccountId,
+    },
+    RemoveTeeKey {
+        address: Vec<u8>,
+        compromised_at_ns: Option<u64>,
+    },
+    SetAiProcessingFee {
+        fee: NearToken,
+    },
+    SetGovernanceDelay {
+        delay_ns: u64,
+    },
+    SetPlatformFee {
+        fee_bps: u16,
+    },
+    SetTreasury {
+        account_id: AccountId,
+    },
+    SetOperatorFeeShare {
+        share_bps: u16,
+    },
+    SetFallbackArbitrator {
+        account_id: Option<AccountId>,
+    },
+    SetAiResolutionTimeout {
+        timeout_ns: u64,
+    },
+    SetTimeWindowBounds {
+        min: TimeWindows,
+        max: TimeWindows,
+    },
+    UpgradeContract {
+        code_hash: Vec<u8>,
+    },
+}
+
+impl GovernanceAction {
+    /// Role allowed to propose, execute and cancel this action besides the owner.
+    pub fn role(&self) -> Option<Role> {
+        match self {
+            GovernanceAction::RegisterTeeKey { .. } | GovernanceAction::RemoveTeeKey { .. } => {
+                Some(Role::TeeRegistryManager)
+            }
+            GovernanceAction::SetAiProcessingFee { .. }
+            | GovernanceAction::SetPlatformFee { .. }
+            | GovernanceAction::SetOperatorFeeShare { .. } => Some(Role::FeeManager),
+            GovernanceAction::UpgradeContract { .. } => Some(Role::Upgrader),
+            GovernanceAction::SetGovernanceDelay { .. }
+            | GovernanceAction::SetTreasury { .. }
+            | GovernanceAction::SetFallbackArbitrator { .. }
+            | GovernanceAction::SetAiResolutionTimeout { .. }
+            | GovernanceAction::SetTimeWindowBounds { .. } => None,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct QueuedAction {
+    pub id: u64,
+    pub action: GovernanceAction,
+    pub proposed_at_ns: u64,
+    pub executable_at_ns: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ArbitrationRequest {
+    pub escrow_contract_id: String,
+    pub milestone_id: String,
+    pub raised_by: AccountId,
+    pub reason: String,
+    pub category: DisputeCategory,
+    pub claimed_remedy: ClaimedRemedy,
+    pub amount: NearToken,
+    pub prompt_hash: String,
+}
+
+/// Everything an arbitration agent needs to build its prompt for one dispute.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DisputeContext {
+    pub contract_id: String,
+    pub milestone_id: String,
+    pub milestone_title: String,
+    pub milestone_description: String,
+    pub amount: NearToken,
+    pub raised_by: AccountId,
+    pub reason: String,
+    pub category: DisputeCategory,
+    pub claimed_remedy: ClaimedRemedy,
+    pub deliverable: Option<Deliverable>,
+    pub statements: Vec<Statement>,
+    pub evidence: Vec<EvidenceCommitment>,
+    pub evidence_root: Option<String>,
+    pub prompt_hash: String,
+    pub model_id: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ArbitrationDecision {
+    pub resolution: Resolution,
+    pub explanation: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ResolutionAcceptance {
+    pub client_accepted: bool,
+    pub freelancer_accepted: bool,
+    pub client_waived: bool,
+    pub freelancer_waived: bool,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
@@ -95,6 +549,81 @@ pub struct EscrowContract {
     pub disputes: Vec<Dispute>,
     pub model_id: String,
     pub security_pool: NearToken,
+    pub fallback_policy: FallbackPolicy,
+    pub arbitrator: Arbitrator,
+    pub ai_fee: NearToken,
+    pub platform_fee_bps: u16,
+    pub time_windows: TimeWindows,
+    pub hold: Option<ContractHold>,
+}
+
+/// A bilateral hold: proposed by one party, in effect once `held_at_ns` is set.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractHold {
+    pub proposed_by: AccountId,
+    pub held_at_ns: Option<u64>,
+    pub client_cancelled: bool,
+    pub freelancer_cancelled: bool,
+}
+
+/// Review and cooldown periods an escrow runs on, fixed at creation.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct TimeWindows {
+    pub payment_review_ns: u64,
+    pub dispute_review_ns: u64,
+    pub payment_cooldown_ns: u64,
+}
+
+impl TimeWindows {
+    pub fn within(&self, min: &TimeWindows, max: &TimeWindows) -> bool {
+        (min.payment_review_ns..=max.payment_review_ns).contains(&self.payment_review_ns)
+            && (min.dispute_review_ns..=max.dispute_review_ns).contains(&self.dispute_review_ns)
+            && (min.payment_cooldown_ns..=max.payment_cooldown_ns)
+                .contains(&self.payment_cooldown_ns)
+    }
+}
+
+impl Dispute {
+    /// Drops the current ruling so the dispute goes back into the pending queue.
+    pub fn reset_for_rearbitration(&mut self, resolution_deadline_ns: u64) {
+        self.status = DisputeStatus::Pending;
+        self.resolution = None;
+        self.explanation = None;
+        self.deadline_ns = None;
+        self.tee_signature = None;
+        self.tee_signing_address = None;
+        self.tee_text = None;
+        self.client_accepted = false;
+        self.freelancer_accepted = false;
+        self.resolution_deadline_ns = Some(resolution_deadline_ns);
+        self.resolved_at_ns = None;
+        self.lease = None;
+        self.jury = None;
+    }
+
+    /// Statements close when the response window runs out or both parties waive it;
+    /// until then the dispute is not handed to an arbitrator.
+    pub fn response_window_closed_at(&self, timestamp_ns: u64) -> bool {
+        (self.client_waived_response && self.freelancer_waived_response)
+            || self.response_deadline_ns.is_none_or(|d| timestamp_ns >= d)
+    }
+
+    pub fn is_leased_at(&self, timestamp_ns: u64) -> bool {
+        self.lease.as_ref().is_some_and(|l| timestamp_ns < l.expires_at_ns)
+    }
+
+    /// A losing party's acceptance waives their right to appeal or override.
+    pub fn client_waived(&self) -> bool {
+        self.client_accepted && self.resolution.as_ref().is_some_and(|r| r.client_loses())
+    }
+
+    pub fn freelancer_waived(&self) -> bool {
+        self.freelancer_accepted && self.resolution.as_ref().is_some_and(|r| r.freelancer_loses())
+    }
 }
 
 impl EscrowContract {
@@ -102,16 +631,127 @@ impl EscrowContract {
         self.milestones.iter().position(|m| m.id == milestone_id)
     }
 
+    /// Index of the dispute on this milestone that is still waiting for a ruling.
+    pub fn find_open_dispute(&self, milestone_id: &str) -> Option<usize> {
+        self.disputes.iter().position(|d| {
+            d.milestone_id == milestone_id
+                && matches!(d.status, DisputeStatus::Pending | DisputeStatus::Escalated)
+        })
+    }
+
+    /// Index of the newest dispute on this milestone with `status`; earlier rounds
+    /// closed by an override stay in the list.
     pub fn find_dispute(&self, milestone_id: &str, status: DisputeStatus) -> Option<usize> {
         self.disputes
             .iter()
-            .position(|d| d.milestone_id == milestone_id && d.status == status)
+            .rposition(|d| d.milestone_id == milestone_id && d.status == status)
+    }
+
+    /// Index of the newest finalized dispute on this milestone whose funds are still held.
+    pub fn find_unreleased_dispute(&self, milestone_id: &str) -> Option<usize> {
+        self.disputes.iter().rposition(|d| {
+            d.milestone_id == milestone_id
+                && d.status == DisputeStatus::Finalized
+                && !d.funds_released
+        })
+    }
+
+    pub fn latest_dispute(&self, milestone_id: &str) -> Option<&Dispute> {
+        self.disputes.iter().rev().find(|d| d.milestone_id == milestone_id)
+    }
+
+    /// Whether a ruling stays within the remedy the client claimed for the dispute.
+    pub fn resolution_within_claim(&self, dispute_idx: usize, resolution: &Resolution) -> bool {
+        let dispute = &self.disputes[dispute_idx];
+        let Some(milestone_idx) = self.find_milestone(&dispute.milestone_id) else {
+            return false;
+        };
+        let amount = self.milestones[milestone_idx].amount;
+        resolution.client_share(amount) <= dispute.claimed_remedy.max_refund(amount)
     }
 
+    /// `resolution` if it stays within the client's claim, otherwise the split
+    /// that refunds exactly up to the claim (continued work for a rework claim).
+    pub fn cap_to_claim(&self, dispute_idx: usize, resolution: Resolution) -> Resolution {
+        if self.resolution_within_claim(dispute_idx, &resolution) {
+            return resolution;
+        }
+        let dispute = &self.disputes[dispute_idx];
+        let milestone_idx = self.find_milestone(&dispute.milestone_id).expect("Milestone not found");
+        let amount = self.milestones[milestone_idx].amount;
+        let max_refund = dispute.claimed_remedy.max_refund(amount).as_yoctonear();
+        if max_refund == 0 {
+            return Resolution::ContinueWork;
+        }
+        let kept = amount.as_yoctonear().saturating_sub(max_refund);
+        Resolution::Split {
+            freelancer_pct: (kept * 100).div_ceil(amount.as_yoctonear()) as u8,
+        }
+    }
+
+    /// Every milestone is closed, either paid out or refunded after non-delivery.
     pub fn all_milestones_completed(&self) -> bool {
         self.milestones
             .iter()
-            .all(|m| m.status == MilestoneStatus::Completed)
+            .all(|m| matches!(m.status, MilestoneStatus::Completed | MilestoneStatus::Refunded))
+    }
+
+    /// Closes out a disputed milestone under `resolution` and returns the
+    /// (freelancer, client) payouts the caller must transfer.
+    pub fn apply_resolution(
+        &mut self,
+        milestone_idx: usize,
+        resolution: &Resolution,
+    ) -> (NearToken, NearToken) {
+        let amount = self.milestones[milestone_idx].amount;
+
+        let (freelancer_amount, client_amount) = match resolution {
+            Resolution::Freelancer => (amount.as_yoctonear(), 0),
+            Resolution::Client => (0, amount.as_yoctonear()),
+            Resolution::ContinueWork => {
+                self.milestones[milestone_idx].status = MilestoneStatus::InProgress;
+                self.milestones[milestone_idx].payment_request_deadline_ns = None;
+                self.status = ContractStatus::Active;
+                return (NearToken::from_yoctonear(0), NearToken::from_yoctonear(0));
+            }
+            Resolution::Split { freelancer_pct } => {
+                assert!(*freelancer_pct <= 100, "Invalid split percentage");
+                let freelancer_amount = amount.as_yoctonear() * *freelancer_pct as u128 / 100;
+                (freelancer_amount, amount.as_yoctonear() - freelancer_amount)
+            }
+        };
+
+        self.milestones[milestone_idx].status = MilestoneStatus::Completed;
+        if self.all_milestones_completed() {
+            self.status = ContractStatus::Resolved;
+        }
+
+        (
+            NearToken::from_yoctonear(freelancer_amount),
+            NearToken::from_yoctonear(client_amount),
+        )
+    }
+
+    /// Recomputes the contract status after a dispute closes without a ruling.
+    pub fn restore_status(&mut self) {
+        self.status = if self
+            .milestones
+            .iter()
+            .any(|m| m.status == MilestoneStatus::Disputed)
+        {
+            ContractStatus::Disputed
+        } else if self.all_milestones_completed() {
+            ContractStatus::Completed
+        } else {
+            ContractStatus::Active
+        };
+    }
+
+    pub fn require_not_on_hold(&self) {
+        assert!(
+            !matches!(self.status, ContractStatus::OnHold | ContractStatus::Cancelled),
+            "Contract is on hold or cancelled"
+        );
     }
 
     pub fn require_freelancer(&self) -> AccountId {
@@ -208,17 +208,30 @@ export const TOOLS: Tool[] = [
 
   {
     name: "request_payment",
-    description: "Request payment for a completed milestone. Sets a 48-hour deadline for client approval. Only freelancer can call.",
+    description: "Request payment for a completed milestone and record the deliverable as a new version. Sets a 48-hour deadline for client approval. Only freelancer can call.",
     inputSchema: {
       type: "object",
       properties: {
         contract_id: { type: "string", description: "The contract ID" },
         milestone_id: { type: "string", description: "The milestone ID" },
+        content_hash: { type: "string", description: "SHA-256 of the deliverable as 64 hex characters" },
+        uri: { type: "string", description: "Where the deliverable can be fetched (e.g., 'ipfs://...', up to 256 bytes)" },
+        note: { type: "string", description: "Optional note for the client" },
       },
-      required: ["contract_id", "milestone_id"],
+      required: ["contract_id", "milestone_id", "content_hash", "uri"],
+    },
+    handler: async (args) => {
+      const hex = String(args.content_hash).replace(/^0x/, "");
+      if (!/^[0-9a-fA-F]{64}$/.test(hex)) throw new Error("content_hash must be a 32-byte hex SHA-256 hash");
+
+      return callMethod("request_payment", {
+        contract_id: args.contract_id,
+        milestone_id: args.milestone_id,
+        content_hash: Array.from(Buffer.from(hex, "hex")),
+        uri: args.uri,
+        note: args.note ?? null,
+      });
     },
-    handler: async (args) =>
-      callMethod("request_payment", { contract_id: args.contract_id, milestone_id: args.milestone_id }),
   },
 
   {
@@ -260,14 +273,37 @@ export const TOOLS: Tool[] = [
         contract_id: { type: "string", description: "The contract ID" },
         milestone_id: { type: "string", description: "The milestone ID" },
         reason: { type: "string", description: "Reason for the dispute" },
+        category: {
+          type: "string",
+          enum: ["NonDelivery", "Quality", "MissedDeadline", "ScopeDisagreement", "ClientUnresponsive"],
+          description: "What the dispute is about",
+        },
+        claimed_remedy: {
+          type: "string",
+          enum: ["FullRefund", "PartialRefund", "Rework"],
+          description: "What the client asks for. Rulings never refund more than the claim.",
+        },
+        refund_amount: {
+          type: "string",
+          description: "Refund claimed in NEAR (e.g., '2.5'). Required when claimed_remedy is PartialRefund.",
+        },
       },
-      required: ["contract_id", "milestone_id", "reason"],
+      required: ["contract_id", "milestone_id", "reason", "category", "claimed_remedy"],
     },
-    handler: async (args) =>
-      callMethod("raise_dispute", {
+    handler: async (args) => {
+      let claimedRemedy: unknown = args.claimed_remedy;
+      if (args.claimed_remedy === "PartialRefund") {
+        if (!args.refund_amount) throw new Error("refund_amount is required for a PartialRefund claim");
+        claimedRemedy = { PartialRefund: { amount: nearToYocto(args.refund_amount as string) } };
+      }
+
+      return callMethod("raise_dispute", {
         contract_id: args.contract_id,
         milestone_id: args.milestone_id,
         reason: args.reason,
-      }),
+        category: args.category,
+        claimed_remedy: claimedRemedy,
+      });
+    },
   },
 ];
@@ -2,10 +2,11 @@
 set -e
 
 # Nescrowee - TEE Address Registration Script
-# Usage: bun run contract:register-tee [testnet|mainnet] [model_id]
+# Usage: bun run contract:register-tee [testnet|mainnet] [model_id[,model_id...]] [operator_account]
 
 NETWORK="${1:-testnet}"
 MODEL="${2:-deepseek-ai/DeepSeek-V3.1}"
+OPERATOR="${3:-$NEAR_ACCOUNT}"
 
 echo "Registering TEE addresses for Nescrowee contract on $NETWORK..."
 echo "Model: $MODEL"
@@ -27,7 +28,7 @@ fi
 
 echo ""
 echo "Fetching TEE attestation from NEAR AI Cloud..."
-ATTESTATION=$(curl -s "https://cloud-api.near.ai/v1/attestation/report?model=$MODEL&signing_algo=ed25519")
+ATTESTATION=$(curl -s "https://cloud-api.near.ai/v1/attestation/report?model=${MODEL%%,*}&signing_algo=ed25519")
 
 SIGNING_ADDRESS=$(echo $ATTESTATION | python3 -c "import sys,json; print(json.load(sys.stdin)['gateway_attestation']['signing_address'])" 2>/dev/null)
 
@@ -46,11 +47,17 @@ print('[' + ','.join(str(b) for b in bytes.fromhex(addr)) + ']')
 ")
 
 echo "Byte array: $BYTES"
+
+ATTESTATION_HASH=$(echo -n "$ATTESTATION" | python3 -c "import sys,hashlib; print(hashlib.sha256(sys.stdin.buffer.read()).hexdigest())")
+echo "Attestation report hash: $ATTESTATION_HASH"
+echo "Operator: $OPERATOR"
+
+MODEL_IDS=$(python3 -c "import json; print(json.dumps('$MODEL'.split(',')))")
 echo ""
 echo "Registering TEE address to contract: $CONTRACT_ACCOUNT"
 
 near contract call-function as-transaction $CONTRACT_ACCOUNT register_tee_address json-args \
-  "{\"address\":$BYTES}" \
+  "{\"address\":$BYTES,\"attestation_hash\":\"$ATTESTATION_HASH\",\"model_ids\":$MODEL_IDS,\"operator\":\"$OPERATOR\"}" \
   prepaid-gas '30 Tgas' \
   attached-deposit '0 NEAR' \
   sign-as $NEAR_ACCOUNT \
@@ -58,8 +65,12 @@ near contract call-function as-transaction $CONTRACT_ACCOUNT register_tee_addres
   sign-with-keychain send
 
 echo ""
-echo "TEE address registered successfully!"
+echo "TEE address registration queued!"
+echo "Once the governance delay has passed, apply it with:"
+echo "  near contract call-function as-transaction $CONTRACT_ACCOUNT execute_action json-args '{\"action_id\":<id>}' ..."
+echo "Queued actions: near contract call-function as-read-only $CONTRACT_ACCOUNT get_queued_actions json-args '{}' network-config $NETWORK"
 echo ""
+echo "The key is only accepted for: $MODEL"
 echo "Available models (all share the same gateway signing key):"
 echo "  - Qwen/Qwen3-30B-A3B-Instruct-2507 (Standard)"
 echo "  - openai/gpt-oss-120b (Strong reasoning)"
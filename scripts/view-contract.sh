@@ -83,7 +83,7 @@ done
 echo ""
 echo "3️⃣  Registered TEE Addresses"
 echo "============================"
-near contract call-function as-read-only $CONTRACT_ACCOUNT get_tee_addresses \
+near contract call-function as-read-only $CONTRACT_ACCOUNT get_tee_registry \
   json-args '{}' \
   network-config $NETWORK
 echo ""
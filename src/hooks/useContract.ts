@@ -11,21 +11,27 @@ import {
   approveMilestone,
   autoApprovePayment,
   raiseDispute,
+  fileStatement,
+  waiveResponseWindow,
   acceptResolution,
   finalizeResolution,
   releaseDisputeFunds,
   overrideToContinueWork,
   completeContractSecurity,
+  getResolutionAcceptance,
   type CreateContractArgs,
 } from "@/near/contract";
 import { submitAiResolution, acceptResolution as acceptResolutionCall } from "@/near/contract";
 import { anonymizeDisputeContext } from "@/utils/anonymize";
+import { isResponseWindowClosed } from "@/utils/dispute";
 import { getChatMessages, sendStructuredMessage } from "@/near/social";
 import type { EvidenceData } from "@/near/social";
 import { retrieveEvidence } from "@/nova/client";
 import { runInvestigation, type InvestigationStep, type OnStepCallback } from "@/investigation/investigation";
 import { signatureToBytes, addressToBytes } from "@/investigation/client";
 import type { EscrowContract } from "@/types/escrow";
+import type { ClaimedRemedy, Dispute, DisputeCategory } from "@/types/dispute";
+import type { DeliverableInput } from "@/types/milestone";
 
 export function useContractDetail(contractId: string | undefined) {
   return useQuery({
@@ -84,8 +90,15 @@ export function useStartMilestone() {
 export function useRequestPayment() {
   const queryClient = useQueryClient();
   return useMutation({
-    mutationFn: ({ contractId, milestoneId }: { contractId: string; milestoneId: string }) =>
-      requestPayment(contractId, milestoneId),
+    mutationFn: ({
+      contractId,
+      milestoneId,
+      deliverable,
+    }: {
+      contractId: string;
+      milestoneId: string;
+      deliverable: DeliverableInput;
+    }) => requestPayment(contractId, milestoneId, deliverable),
     onSuccess: (_data, variables) =>
       queryClient.invalidateQueries({ queryKey: ["contract", variables.contractId] }),
   });
@@ -128,48 +141,72 @@ export function useRaiseDispute() {
       contractId,
       milestoneId,
       reason,
+      category,
+      claimedRemedy,
     }: {
       contractId: string;
       milestoneId: string;
       reason: string;
-    }) => raiseDispute(contractId, milestoneId, reason),
+      category: DisputeCategory;
+      claimedRemedy: ClaimedRemedy;
+    }) => raiseDispute(contractId, milestoneId, reason, category, claimedRemedy),
     onSuccess: (_data, variables) =>
       queryClient.invalidateQueries({ queryKey: ["contract", variables.contractId] }),
   });
 }
 
+const NOVA_LOCATOR = "nova://";
+
+function toHex(bytes: ArrayLike<number>): string {
+  return Array.from(bytes, (b) => b.toString(16).padStart(2, "0")).join("");
+}
+
+/** Gathers the dispute's on-chain evidence commitments. NOVA files are fetched and
+ * checked against their committed hash; everything else is listed by locator. */
 async function collectEvidence(
   contract: EscrowContract,
+  dispute: Dispute,
   accountId: string | null | undefined,
   onStep?: OnStepCallback
 ): Promise<Array<{ fileName: string; content: string }> | undefined> {
-  if (!accountId) return undefined;
+  if (dispute.evidence.length === 0) return undefined;
 
   onStep?.("collecting_evidence");
+  const fileNames = new Map<string, string>();
   try {
     const allMessages = await getChatMessages(contract.id);
-    const evidenceMessages = allMessages.filter((m) => m.type === "evidence" && m.data);
-    const textEvidence: Array<{ fileName: string; content: string }> = [];
+    for (const msg of allMessages) {
+      const data = msg.data as EvidenceData | undefined;
+      if (msg.type === "evidence" && data?.cid) fileNames.set(data.cid, data.fileName);
+    }
+  } catch { /* names are only cosmetic */ }
 
-    for (const msg of evidenceMessages) {
-      const data = msg.data as EvidenceData;
-      if (!data.cid) continue;
-      const isTextFile = /\.(txt|md|csv|json|log)$/i.test(data.fileName);
-      if (!isTextFile) continue;
+  const evidence: Array<{ fileName: string; content: string }> = [];
+  for (const commitment of dispute.evidence) {
+    const cid = commitment.locator.startsWith(NOVA_LOCATOR)
+      ? commitment.locator.slice(NOVA_LOCATOR.length)
+      : null;
+    const fileName = (cid && fileNames.get(cid)) || commitment.locator;
+    let content = "(held off-chain, not retrieved)";
 
+    if (cid && accountId) {
       try {
-        const buffer = await retrieveEvidence(accountId, contract.id, data.cid);
-        textEvidence.push({
-          fileName: data.fileName,
-          content: new TextDecoder().decode(buffer),
-        });
-      } catch { /* skip files we can't decrypt */ }
+        const buffer = await retrieveEvidence(accountId, contract.id, cid);
+        const digest = new Uint8Array(await crypto.subtle.digest("SHA-256", buffer));
+        if (toHex(digest) !== toHex(commitment.content_hash)) {
+          content = "(retrieved file does not match its on-chain hash)";
+        } else if (/\.(txt|md|csv|json|log)$/i.test(fileName)) {
+          content = new TextDecoder().decode(buffer);
+        } else {
+          content = "(binary file, verified against its on-chain hash)";
+        }
+      } catch { /* keep the placeholder for files we can't decrypt */ }
     }
 
-    return textEvidence.length > 0 ? textEvidence : undefined;
-  } catch {
-    return undefined;
+    evidence.push({ fileName, content });
   }
+
+  return evidence;
 }
 
 function buildAnonymizedContext(
@@ -197,6 +234,7 @@ function buildAnonymizedContext(
       raised_by: dispute.raised_by,
       reason: dispute.reason,
     },
+    statements: dispute.statements,
     chatHistory,
     evidence,
   });
@@ -273,8 +311,11 @@ export function useRunInvestigation() {
         (d) => d.milestone_id === milestoneId && d.status === "Pending",
       );
       if (!dispute || !milestone) throw new Error("Dispute or milestone not found");
+      if (!isResponseWindowClosed(dispute)) {
+        throw new Error("The parties' response window is still open");
+      }
 
-      const evidence = await collectEvidence(fresh, accountId, onStep);
+      const evidence = await collectEvidence(fresh, dispute, accountId, onStep);
       const context = buildAnonymizedContext(fresh, milestone, dispute, chatHistory, evidence, onStep);
 
       onStep?.("connecting_tee");
@@ -300,6 +341,26 @@ export function useRunInvestigation() {
 
 export type { InvestigationStep };
 
+export function useFileStatement() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ contractId, milestoneId, text }: { contractId: string; milestoneId: string; text: string }) =>
+      fileStatement(contractId, milestoneId, text),
+    onSuccess: (_data, variables) =>
+      queryClient.invalidateQueries({ queryKey: ["contract", variables.contractId] }),
+  });
+}
+
+export function useWaiveResponseWindow() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ contractId, milestoneId }: { contractId: string; milestoneId: string }) =>
+      waiveResponseWindow(contractId, milestoneId),
+    onSuccess: (_data, variables) =>
+      queryClient.invalidateQueries({ queryKey: ["contract", variables.contractId] }),
+  });
+}
+
 export function useAcceptResolution() {
   const queryClient = useQueryClient();
   return useMutation({
@@ -345,7 +406,11 @@ export function useAcceptAndRelease() {
   return useMutation({
     mutationFn: async ({ contractId, milestoneId }: { contractId: string; milestoneId: string }) => {
       await acceptResolutionCall(contractId, milestoneId);
+      // The ruling is final only once both parties have accepted it.
+      const acceptance = await getResolutionAcceptance(contractId, milestoneId);
+      if (!acceptance?.client_accepted || !acceptance.freelancer_accepted) return false;
       await releaseDisputeFunds(contractId, milestoneId);
+      return true;
     },
     onSuccess: (_data, variables) =>
       queryClient.invalidateQueries({ queryKey: ["contract", variables.contractId] }),
@@ -1,6 +1,7 @@
 import { nearConfig } from "./config";
 import type { EscrowContract } from "@/types/escrow";
-import type { Dispute } from "@/types/dispute";
+import type { ClaimedRemedy, Dispute, DisputeCategory, ResolutionAcceptance } from "@/types/dispute";
+import type { DeliverableInput } from "@/types/milestone";
 
 const GAS = "300000000000000";
 const NO_DEPOSIT = "0";
@@ -56,6 +57,16 @@ export async function getDispute(contractId: string, milestoneId: string): Promi
   return viewMethod<Dispute | null>("get_dispute", { contract_id: contractId, milestone_id: milestoneId });
 }
 
+export async function getResolutionAcceptance(
+  contractId: string,
+  milestoneId: string,
+): Promise<ResolutionAcceptance | null> {
+  return viewMethod<ResolutionAcceptance | null>("get_resolution_acceptance", {
+    contract_id: contractId,
+    milestone_id: milestoneId,
+  });
+}
+
 export async function getPromptHash(contractId: string): Promise<string | null> {
   return viewMethod<string | null>("get_prompt_hash", { contract_id: contractId });
 }
@@ -102,8 +113,14 @@ export function startMilestone(contractId: string, milestoneId: string) {
   return callMethod("start_milestone", { contract_id: contractId, milestone_id: milestoneId });
 }
 
-export function requestPayment(contractId: string, milestoneId: string) {
-  return callMethod("request_payment", { contract_id: contractId, milestone_id: milestoneId });
+export function requestPayment(contractId: string, milestoneId: string, deliverable: DeliverableInput) {
+  return callMethod("request_payment", {
+    contract_id: contractId,
+    milestone_id: milestoneId,
+    content_hash: deliverable.contentHash,
+    uri: deliverable.uri,
+    note: deliverable.note ?? null,
+  });
 }
 
 export function cancelPaymentRequest(contractId: string, milestoneId: string) {
@@ -118,11 +135,19 @@ export function autoApprovePayment(contractId: string, milestoneId: string) {
   return callMethod("auto_approve_payment", { contract_id: contractId, milestone_id: milestoneId });
 }
 
-export function raiseDispute(contractId: string, milestoneId: string, reason: string) {
+export function raiseDispute(
+  contractId: string,
+  milestoneId: string,
+  reason: string,
+  category: DisputeCategory,
+  claimedRemedy: ClaimedRemedy,
+) {
   return callMethod("raise_dispute", {
     contract_id: contractId,
     milestone_id: milestoneId,
     reason,
+    category,
+    claimed_remedy: claimedRemedy,
   });
 }
 
@@ -146,6 +171,33 @@ export function submitAiResolution(
   });
 }
 
+export function submitEvidence(
+  contractId: string,
+  milestoneId: string,
+  contentHash: number[],
+  locator: string,
+) {
+  return callMethod("submit_evidence", {
+    contract_id: contractId,
+    milestone_id: milestoneId,
+    content_hash: contentHash,
+    locator,
+  });
+}
+
+export function fileStatement(contractId: string, milestoneId: string, text: string) {
+  return callMethod("file_statement", {
+    contract_id: contractId,
+    milestone_id: milestoneId,
+    text,
+    content_hash: null,
+  });
+}
+
+export function waiveResponseWindow(contractId: string, milestoneId: string) {
+  return callMethod("waive_response_window", { contract_id: contractId, milestone_id: milestoneId });
+}
+
 export function acceptResolution(contractId: string, milestoneId: string) {
   return callMethod("accept_resolution", { contract_id: contractId, milestone_id: milestoneId });
 }
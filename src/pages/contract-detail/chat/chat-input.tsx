@@ -6,6 +6,8 @@ import type { EscrowContract } from "@/types/escrow";
 import { sendStructuredMessage } from "@/near/social";
 import { uploadEvidence as novaUpload, createEvidenceVault, isVaultMember } from "@/nova/client";
 import type { EvidenceData } from "@/near/social";
+import { submitEvidence } from "@/near/contract";
+import { isResponseWindowClosed } from "@/utils/dispute";
 
 interface ChatInputProps {
   onSend: (content: string) => Promise<void> | void;
@@ -67,6 +69,26 @@ export function ChatInput({
         "evidence",
         evidence as unknown as Record<string, unknown>,
       );
+
+      // Files uploaded while a dispute accepts evidence are committed on-chain, which
+      // is the set the arbitrator reviews.
+      const now = Date.now() * 1e6;
+      const openDispute = contract.disputes.find(
+        (d) =>
+          d.status === "Pending" &&
+          d.evidence_deadline_ns !== null &&
+          now < d.evidence_deadline_ns &&
+          !isResponseWindowClosed(d, now),
+      );
+      if (openDispute) {
+        const digest = await crypto.subtle.digest("SHA-256", fileBuffer);
+        await submitEvidence(
+          contract.id,
+          openDispute.milestone_id,
+          Array.from(new Uint8Array(digest)),
+          `nova://${result.cid}`,
+        );
+      }
       toast.success(`Evidence encrypted & uploaded via NOVA`);
       onEvidenceUploaded?.();
     } catch (err) {
@@ -79,7 +79,7 @@ const ContractDetailPage = () => {
     if (milestoneId) triggerAiResolution(milestoneId);
   }, [triggerAiResolution]);
 
-  const { actions, pending } = useContractActions(contract, triggerAiResolution);
+  const { actions, pending } = useContractActions(contract);
 
   useEffect(() => {
     if (!contract) return;
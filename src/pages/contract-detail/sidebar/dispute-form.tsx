@@ -1,10 +1,26 @@
+import { useState } from "react";
 import { Button } from "@/components/ui/button";
 import { Textarea } from "@/components/ui/textarea";
+import { nearToYocto } from "@/pages/create-contract/utils";
+import type { ClaimedRemedy, DisputeCategory } from "@/types/dispute";
+
+const CATEGORIES: { value: DisputeCategory; label: string }[] = [
+  { value: "Quality", label: "Quality" },
+  { value: "NonDelivery", label: "Non-delivery" },
+  { value: "MissedDeadline", label: "Missed deadline" },
+  { value: "ScopeDisagreement", label: "Scope disagreement" },
+  { value: "ClientUnresponsive", label: "Client unresponsive" },
+];
+
+type RemedyKind = "FullRefund" | "PartialRefund" | "Rework";
+
+const selectClassName =
+  "h-9 w-full bg-white/5 rounded-2xl px-3 py-2 text-sm text-white focus:outline-none focus:bg-white/10 transition-colors border-0 cursor-pointer";
 
 interface DisputeFormProps {
   reason: string;
   onReasonChange: (reason: string) => void;
-  onSubmit: () => void;
+  onSubmit: (category: DisputeCategory, claimedRemedy: ClaimedRemedy) => void;
   onCancel: () => void;
   isPending: boolean;
 }
@@ -16,9 +32,56 @@ export function DisputeForm({
   onCancel,
   isPending,
 }: DisputeFormProps) {
+  const [category, setCategory] = useState<DisputeCategory>("Quality");
+  const [remedyKind, setRemedyKind] = useState<RemedyKind>("FullRefund");
+  const [partialNear, setPartialNear] = useState("");
+
+  const partialValid = remedyKind !== "PartialRefund" || Number(partialNear) > 0;
+
+  const handleSubmit = () => {
+    const claimedRemedy: ClaimedRemedy =
+      remedyKind === "PartialRefund"
+        ? { PartialRefund: { amount: nearToYocto(parseFloat(partialNear)) } }
+        : remedyKind;
+    onSubmit(category, claimedRemedy);
+  };
+
   return (
     <div className="pt-4 border-t border-white/12 space-y-3">
       <h3 className="text-sm font-bold text-red-300">Raise Dispute</h3>
+      <select
+        value={category}
+        onChange={(e) => setCategory(e.target.value as DisputeCategory)}
+        className={selectClassName}
+        aria-label="Dispute category"
+      >
+        {CATEGORIES.map((c) => (
+          <option key={c.value} value={c.value} className="bg-card text-foreground">
+            {c.label}
+          </option>
+        ))}
+      </select>
+      <select
+        value={remedyKind}
+        onChange={(e) => setRemedyKind(e.target.value as RemedyKind)}
+        className={selectClassName}
+        aria-label="Remedy claimed"
+      >
+        <option value="FullRefund" className="bg-card text-foreground">Full refund</option>
+        <option value="PartialRefund" className="bg-card text-foreground">Partial refund</option>
+        <option value="Rework" className="bg-card text-foreground">Rework only</option>
+      </select>
+      {remedyKind === "PartialRefund" && (
+        <input
+          type="number"
+          min="0"
+          step="0.01"
+          placeholder="Refund amount (NEAR)"
+          value={partialNear}
+          onChange={(e) => setPartialNear(e.target.value)}
+          className="h-9 w-full text-sm font-mono bg-white/5 rounded-2xl px-4 py-2 text-white placeholder-white/30 focus:outline-none focus:bg-white/10 transition-colors border-0"
+        />
+      )}
       <Textarea
         placeholder="Explain the reason for this dispute..."
         value={reason}
@@ -29,8 +92,8 @@ export function DisputeForm({
         <Button
           size="sm"
           variant="destructive"
-          onClick={onSubmit}
-          disabled={isPending || !reason.trim()}
+          onClick={handleSubmit}
+          disabled={isPending || !reason.trim() || !partialValid}
         >
           Submit Dispute
         </Button>
@@ -1,9 +1,12 @@
+import { useState } from "react";
 import { Button } from "@/components/ui/button";
+import { Textarea } from "@/components/ui/textarea";
 import StatusBadge from "@/components/status-badge";
 import { HugeiconsIcon } from "@hugeicons/react";
 import { Alert01Icon, CheckmarkCircle01Icon, AiBrain01Icon } from "@hugeicons/core-free-icons";
 import type { Dispute } from "@/types/dispute";
 import type { Resolution } from "@/types/dispute";
+import { isResponseWindowClosed } from "@/utils/dispute";
 
 function formatResolution(res: Resolution): string {
   if (res === "Freelancer") return "Full payment to freelancer";
@@ -23,10 +26,85 @@ interface DisputeItemProps {
   onOverrideToContinueWork: (milestoneId: string) => void;
   onAcceptAndRelease: (milestoneId: string) => void;
   onRunInvestigation?: (milestoneId: string) => void;
+  onFileStatement: (milestoneId: string, text: string) => void;
+  onWaiveResponseWindow: (milestoneId: string) => void;
   acceptPending: boolean;
   releaseFundsPending: boolean;
   overrideContinuePending: boolean;
   acceptAndReleasePending: boolean;
+  statementPending: boolean;
+  waiveResponsePending: boolean;
+}
+
+function ResponseWindow({
+  dispute,
+  userRole,
+  onFileStatement,
+  onWaiveResponseWindow,
+  statementPending,
+  waiveResponsePending,
+}: {
+  dispute: Dispute;
+  userRole: "client" | "freelancer" | null;
+  onFileStatement: (milestoneId: string, text: string) => void;
+  onWaiveResponseWindow: (milestoneId: string) => void;
+  statementPending: boolean;
+  waiveResponsePending: boolean;
+}) {
+  const [text, setText] = useState("");
+  const waived =
+    userRole === "client" ? dispute.client_waived_response : dispute.freelancer_waived_response;
+
+  const handleFile = () => {
+    onFileStatement(dispute.milestone_id, text.trim());
+    setText("");
+  };
+
+  return (
+    <div className="space-y-2">
+      {dispute.response_deadline_ns && (
+        <p className="text-xs font-mono text-white/50">
+          Statements open until {new Date(dispute.response_deadline_ns / 1e6).toLocaleString()}
+        </p>
+      )}
+      {userRole && !waived && (
+        <>
+          <Textarea
+            placeholder="Add your statement for the arbitrator..."
+            value={text}
+            maxLength={2000}
+            onChange={(e) => setText(e.target.value)}
+            className="min-h-[60px] resize-none bg-white/5 rounded-2xl px-4 py-3 text-sm text-white placeholder-white/30 focus:bg-white/10 border-0"
+          />
+          <div className="flex gap-2">
+            <Button
+              size="sm"
+              variant="outline"
+              className="text-white/80 border-white/10 hover:bg-white/10"
+              onClick={handleFile}
+              disabled={statementPending || !text.trim()}
+            >
+              File Statement
+            </Button>
+            <Button
+              size="sm"
+              variant="ghost"
+              className="text-white/60 hover:bg-white/10"
+              onClick={() => onWaiveResponseWindow(dispute.milestone_id)}
+              disabled={waiveResponsePending}
+            >
+              Done, skip the wait
+            </Button>
+          </div>
+        </>
+      )}
+      {waived && (
+        <p className="text-xs font-mono text-white/50">
+          Waiting for the other party or the end of the window...
+        </p>
+      )}
+    </div>
+  );
 }
 
 function ResolutionActions({
@@ -136,10 +214,14 @@ export function DisputeItem({
   onOverrideToContinueWork,
   onAcceptAndRelease,
   onRunInvestigation,
+  onFileStatement,
+  onWaiveResponseWindow,
   acceptPending,
   releaseFundsPending,
   overrideContinuePending,
   acceptAndReleasePending,
+  statementPending,
+  waiveResponsePending,
 }: DisputeItemProps) {
   return (
     <div className="p-4 rounded-2xl border border-red-500/20 space-y-3">
@@ -149,6 +231,17 @@ export function DisputeItem({
       </div>
       <p className="text-sm text-white/90">{dispute.reason}</p>
 
+      {dispute.statements.length > 0 && (
+        <div className="space-y-1">
+          {dispute.statements.map((s, i) => (
+            <p key={i} className="text-xs text-white/70">
+              <span className="font-mono text-white/50">{s.author}:</span>{" "}
+              {s.text ?? "Statement filed off-chain"}
+            </p>
+          ))}
+        </div>
+      )}
+
       {dispute.resolution && dispute.status === "AiResolved" && (
         <div className="p-3 rounded-xl border border-white/10 space-y-2">
           <p className="text-xs text-purple-300 font-mono flex items-center gap-1">
@@ -184,7 +277,16 @@ export function DisputeItem({
 
       {dispute.status === "Pending" && (
         <div className="space-y-2">
-          {aiProcessing === "active" ? (
+          {!isResponseWindowClosed(dispute) ? (
+            <ResponseWindow
+              dispute={dispute}
+              userRole={userRole}
+              onFileStatement={onFileStatement}
+              onWaiveResponseWindow={onWaiveResponseWindow}
+              statementPending={statementPending}
+              waiveResponsePending={waiveResponsePending}
+            />
+          ) : aiProcessing === "active" ? (
             <div className="flex items-center gap-2">
               <div className="w-2 h-2 rounded-full bg-purple-400 animate-pulse" />
               <p className="text-xs font-mono text-purple-300">
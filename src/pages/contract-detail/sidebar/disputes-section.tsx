@@ -35,10 +35,14 @@ export function DisputesSection({
           onOverrideToContinueWork={actions.overrideToContinueWork}
           onAcceptAndRelease={actions.acceptAndRelease}
           onRunInvestigation={userRole === "client" ? onRunInvestigation : undefined}
+          onFileStatement={actions.fileStatement}
+          onWaiveResponseWindow={actions.waiveResponseWindow}
           acceptPending={pending.accept}
           releaseFundsPending={pending.releaseFunds}
           overrideContinuePending={pending.overrideContinue}
           acceptAndReleasePending={pending.acceptAndRelease}
+          statementPending={pending.statement}
+          waiveResponsePending={pending.waiveResponse}
         />
       ))}
     </div>
@@ -6,11 +6,14 @@ import {
   ArrowRight01Icon,
 } from "@hugeicons/core-free-icons";
 import type { EscrowContract } from "@/types/escrow";
+import type { ClaimedRemedy, DisputeCategory } from "@/types/dispute";
+import type { DeliverableInput } from "@/types/milestone";
 import type { ContractActions, ActionsPending } from "../useContractActions";
 import { ContractInfo } from "./contract-info";
 import { MilestonesList } from "./milestones-list";
 import { DisputesSection } from "./disputes-section";
 import { DisputeForm } from "./dispute-form";
+import { PaymentRequestForm } from "./payment-request-form";
 import { yoctoToNear } from "@/utils/format";
 
 interface SidebarProps {
@@ -33,6 +36,7 @@ export function Sidebar({
   const [sidebarOpen, setSidebarOpen] = useState(true);
   const [disputeMilestoneId, setDisputeMilestoneId] = useState<string | null>(null);
   const [disputeReason, setDisputeReason] = useState("");
+  const [paymentMilestoneId, setPaymentMilestoneId] = useState<string | null>(null);
 
   const completed = contract.milestones.filter((m) => m.status === "Completed").length;
   const allCompleted = contract.milestones.every((m) => m.status === "Completed");
@@ -40,13 +44,19 @@ export function Sidebar({
   const isClient = userRole === "client";
   const isFreelancer = userRole === "freelancer";
 
-  const handleDisputeSubmit = () => {
+  const handleDisputeSubmit = (category: DisputeCategory, claimedRemedy: ClaimedRemedy) => {
     if (!disputeMilestoneId || !disputeReason.trim()) return;
-    actions.raiseDispute(disputeMilestoneId, disputeReason);
+    actions.raiseDispute(disputeMilestoneId, disputeReason, category, claimedRemedy);
     setDisputeReason("");
     setDisputeMilestoneId(null);
   };
 
+  const handlePaymentRequestSubmit = (deliverable: DeliverableInput) => {
+    if (!paymentMilestoneId) return;
+    actions.requestPayment(paymentMilestoneId, deliverable);
+    setPaymentMilestoneId(null);
+  };
+
   const handleDisputeCancel = () => {
     setDisputeMilestoneId(null);
     setDisputeReason("");
@@ -97,8 +107,17 @@ export function Sidebar({
                 actions={actions}
                 pending={pending}
                 onOpenDispute={setDisputeMilestoneId}
+                onOpenPaymentRequest={setPaymentMilestoneId}
               />
 
+              {paymentMilestoneId && (
+                <PaymentRequestForm
+                  onSubmit={handlePaymentRequestSubmit}
+                  onCancel={() => setPaymentMilestoneId(null)}
+                  isPending={pending.requestPayment}
+                />
+              )}
+
               {disputeMilestoneId && (
                 <DisputeForm
                   reason={disputeReason}
@@ -31,6 +31,7 @@ interface MilestoneItemProps {
   actions: ContractActions;
   pending: ActionsPending;
   onOpenDispute: (milestoneId: string) => void;
+  onOpenPaymentRequest: (milestoneId: string) => void;
 }
 
 export function MilestoneItem({
@@ -43,6 +44,7 @@ export function MilestoneItem({
   actions,
   pending,
   onOpenDispute,
+  onOpenPaymentRequest,
 }: MilestoneItemProps) {
   const deadlineExpired = milestone.payment_request_deadline_ns
     ? Date.now() * 1e6 >= milestone.payment_request_deadline_ns
@@ -98,7 +100,7 @@ export function MilestoneItem({
             <Button
               size="sm"
               variant="hero"
-              onClick={() => actions.requestPayment(milestone.id)}
+              onClick={() => onOpenPaymentRequest(milestone.id)}
               disabled={pending.requestPayment || cooldownActive}
             >
               Request Payment
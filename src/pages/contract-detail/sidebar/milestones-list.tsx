@@ -10,6 +10,7 @@ interface MilestonesListProps {
   actions: ContractActions;
   pending: ActionsPending;
   onOpenDispute: (milestoneId: string) => void;
+  onOpenPaymentRequest: (milestoneId: string) => void;
 }
 
 export function MilestonesList({
@@ -19,6 +20,7 @@ export function MilestonesList({
   actions,
   pending,
   onOpenDispute,
+  onOpenPaymentRequest,
 }: MilestonesListProps) {
   const completed = contract.milestones.filter((m) => m.status === "Completed").length;
   const progress = contract.milestones.length > 0 ? (completed / contract.milestones.length) * 100 : 0;
@@ -45,6 +47,7 @@ export function MilestonesList({
             actions={actions}
             pending={pending}
             onOpenDispute={onOpenDispute}
+            onOpenPaymentRequest={onOpenPaymentRequest}
           />
         ))}
       </div>
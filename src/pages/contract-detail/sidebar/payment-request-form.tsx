@@ -0,0 +1,77 @@
+import { useState } from "react";
+import { Button } from "@/components/ui/button";
+import { Textarea } from "@/components/ui/textarea";
+import type { DeliverableInput } from "@/types/milestone";
+
+const inputClassName =
+  "h-9 w-full text-sm bg-white/5 rounded-2xl px-4 py-2 text-white placeholder-white/30 focus:outline-none focus:bg-white/10 transition-colors border-0";
+
+async function hashFile(file: File): Promise<number[]> {
+  const hashBuffer = await crypto.subtle.digest("SHA-256", await file.arrayBuffer());
+  return Array.from(new Uint8Array(hashBuffer));
+}
+
+interface PaymentRequestFormProps {
+  onSubmit: (deliverable: DeliverableInput) => void;
+  onCancel: () => void;
+  isPending: boolean;
+}
+
+export function PaymentRequestForm({ onSubmit, onCancel, isPending }: PaymentRequestFormProps) {
+  const [file, setFile] = useState<File | null>(null);
+  const [uri, setUri] = useState("");
+  const [note, setNote] = useState("");
+
+  const handleSubmit = async () => {
+    if (!file || !uri.trim()) return;
+    onSubmit({
+      contentHash: await hashFile(file),
+      uri: uri.trim(),
+      note: note.trim() || undefined,
+    });
+  };
+
+  return (
+    <div className="pt-4 border-t border-white/12 space-y-3">
+      <h3 className="text-sm font-bold text-white">Request Payment</h3>
+      <input
+        type="file"
+        onChange={(e) => setFile(e.target.files?.[0] ?? null)}
+        className="w-full text-xs text-white/70 file:mr-3 file:rounded-xl file:border-0 file:bg-white/10 file:px-3 file:py-1 file:text-white"
+        aria-label="Deliverable file"
+      />
+      <input
+        type="text"
+        placeholder="Where the client can fetch it (ipfs://, https://)"
+        value={uri}
+        onChange={(e) => setUri(e.target.value)}
+        maxLength={256}
+        className={inputClassName}
+      />
+      <Textarea
+        placeholder="Note for the client (optional)"
+        value={note}
+        onChange={(e) => setNote(e.target.value)}
+        className="min-h-[60px] resize-none bg-white/5 rounded-2xl px-5 py-4 text-white placeholder-white/30 focus:bg-white/10 border-0"
+      />
+      <div className="flex gap-2">
+        <Button
+          size="sm"
+          variant="hero"
+          onClick={handleSubmit}
+          disabled={isPending || !file || !uri.trim()}
+        >
+          Submit Deliverable
+        </Button>
+        <Button
+          size="sm"
+          variant="ghost"
+          className="text-white/80 hover:bg-white/10"
+          onClick={onCancel}
+        >
+          Cancel
+        </Button>
+      </div>
+    </div>
+  );
+}
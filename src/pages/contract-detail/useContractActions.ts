@@ -9,6 +9,8 @@ import {
   useApproveMilestone,
   useAutoApprovePayment,
   useRaiseDispute,
+  useFileStatement,
+  useWaiveResponseWindow,
   useAcceptResolution,
   useReleaseDisputeFunds,
   useOverrideToContinueWork,
@@ -17,15 +19,24 @@ import {
   useCompleteContractSecurity,
 } from "@/hooks/useContract";
 import type { EscrowContract } from "@/types/escrow";
+import type { ClaimedRemedy, DisputeCategory } from "@/types/dispute";
+import type { DeliverableInput } from "@/types/milestone";
 
 export interface ContractActions {
   fund: (milestoneId: string) => void;
   start: (milestoneId: string) => void;
-  requestPayment: (milestoneId: string) => void;
+  requestPayment: (milestoneId: string, deliverable: DeliverableInput) => void;
   cancelPaymentRequest: (milestoneId: string) => void;
   approve: (milestoneId: string) => void;
   autoApprove: (milestoneId: string) => void;
-  raiseDispute: (milestoneId: string, reason: string) => void;
+  raiseDispute: (
+    milestoneId: string,
+    reason: string,
+    category: DisputeCategory,
+    claimedRemedy: ClaimedRemedy,
+  ) => void;
+  fileStatement: (milestoneId: string, text: string) => void;
+  waiveResponseWindow: (milestoneId: string) => void;
   acceptResolution: (milestoneId: string) => void;
   releaseFunds: (milestoneId: string) => void;
   overrideToContinueWork: (milestoneId: string) => void;
@@ -42,6 +53,8 @@ export interface ActionsPending {
   approve: boolean;
   autoApprove: boolean;
   dispute: boolean;
+  statement: boolean;
+  waiveResponse: boolean;
   accept: boolean;
   releaseFunds: boolean;
   overrideContinue: boolean;
@@ -50,10 +63,7 @@ export interface ActionsPending {
   security: boolean;
 }
 
-export function useContractActions(
-  contract: EscrowContract | null | undefined,
-  onDisputeRaised?: (milestoneId: string) => void,
-) {
+export function useContractActions(contract: EscrowContract | null | undefined) {
   const fundMutation = useFundContract();
   const startMutation = useStartMilestone();
   const requestPaymentMutation = useRequestPayment();
@@ -61,6 +71,8 @@ export function useContractActions(
   const approveMutation = useApproveMilestone();
   const autoApproveMutation = useAutoApprovePayment();
   const disputeMutation = useRaiseDispute();
+  const statementMutation = useFileStatement();
+  const waiveResponseMutation = useWaiveResponseWindow();
   const acceptMutation = useAcceptResolution();
   const releaseFundsMutation = useReleaseDisputeFunds();
   const overrideContinueMutation = useOverrideToContinueWork();
@@ -93,9 +105,9 @@ export function useContractActions(
       );
     }, [contractId, startMutation]),
 
-    requestPayment: useCallback((milestoneId: string) => {
+    requestPayment: useCallback((milestoneId: string, deliverable: DeliverableInput) => {
       requestPaymentMutation.mutate(
-        { contractId, milestoneId },
+        { contractId, milestoneId, deliverable },
         {
           onSuccess: () => toast.success("Payment requested — client has 48h to review"),
           onError: (e) => toast.error(formatWalletError(e)),
@@ -133,18 +145,40 @@ export function useContractActions(
       );
     }, [contractId, autoApproveMutation]),
 
-    raiseDispute: useCallback((milestoneId: string, reason: string) => {
+    raiseDispute: useCallback((
+      milestoneId: string,
+      reason: string,
+      category: DisputeCategory,
+      claimedRemedy: ClaimedRemedy,
+    ) => {
       disputeMutation.mutate(
-        { contractId, milestoneId, reason },
+        { contractId, milestoneId, reason, category, claimedRemedy },
+        {
+          onSuccess: () => toast.success("Dispute raised — both parties can now file statements"),
+          onError: (e) => toast.error(formatWalletError(e)),
+        },
+      );
+    }, [contractId, disputeMutation]),
+
+    fileStatement: useCallback((milestoneId: string, text: string) => {
+      statementMutation.mutate(
+        { contractId, milestoneId, text },
+        {
+          onSuccess: () => toast.success("Statement filed"),
+          onError: (e) => toast.error(formatWalletError(e)),
+        },
+      );
+    }, [contractId, statementMutation]),
+
+    waiveResponseWindow: useCallback((milestoneId: string) => {
+      waiveResponseMutation.mutate(
+        { contractId, milestoneId },
         {
-          onSuccess: () => {
-            toast.success("Dispute raised — starting AI investigation...");
-            onDisputeRaised?.(milestoneId);
-          },
+          onSuccess: () => toast.success("Response window waived"),
           onError: (e) => toast.error(formatWalletError(e)),
         },
       );
-    }, [contractId, disputeMutation, onDisputeRaised]),
+    }, [contractId, waiveResponseMutation]),
 
     acceptResolution: useCallback((milestoneId: string) => {
       acceptMutation.mutate(
@@ -180,7 +214,12 @@ export function useContractActions(
       acceptAndReleaseMutation.mutate(
         { contractId, milestoneId },
         {
-          onSuccess: () => toast.success("Resolution accepted and funds released"),
+          onSuccess: (released) =>
+            toast.success(
+              released
+                ? "Resolution accepted and funds released"
+                : "Resolution accepted, waiting for the other party",
+            ),
           onError: (e) => toast.error(formatWalletError(e)),
         },
       );
@@ -215,6 +254,8 @@ export function useContractActions(
     approve: approveMutation.isPending,
     autoApprove: autoApproveMutation.isPending,
     dispute: disputeMutation.isPending,
+    statement: statementMutation.isPending,
+    waiveResponse: waiveResponseMutation.isPending,
     accept: acceptMutation.isPending,
     releaseFunds: releaseFundsMutation.isPending,
     overrideContinue: overrideContinueMutation.isPending,
@@ -181,16 +181,25 @@ export const CHANGE_METHODS: ApiMethodData[] = [
   {
     name: "request_payment",
     type: "change",
-    description: "Request payment for a completed milestone. Sets 48-hour deadline for client approval. Only freelancer can call.",
+    description: "Request payment for a completed milestone and record the deliverable as a new version. Sets 48-hour deadline for client approval. Only freelancer can call.",
     parameters: [
       { name: "contract_id", type: "string", description: "The contract ID", required: true },
       { name: "milestone_id", type: "string", description: "The milestone ID", required: true },
+      { name: "content_hash", type: "Vec<u8>", description: "32-byte SHA-256 hash of the deliverable", required: true },
+      { name: "uri", type: "string", description: "Where the deliverable can be fetched (1-256 bytes)", required: true },
+      { name: "note", type: "string | null", description: "Optional note for the client", required: false },
     ],
     returns: "void",
     example: `await account.functionCall({
   contractId: "nescrowee.testnet",
   methodName: "request_payment",
-  args: { contract_id: "c1", milestone_id: "m1" },
+  args: {
+    contract_id: "c1",
+    milestone_id: "m1",
+    content_hash: [/* 32 bytes */],
+    uri: "ipfs://bafy...",
+    note: null
+  },
   gas: "300000000000000"
 });`,
   },
@@ -250,6 +259,8 @@ export const CHANGE_METHODS: ApiMethodData[] = [
       { name: "contract_id", type: "string", description: "The contract ID", required: true },
       { name: "milestone_id", type: "string", description: "The milestone ID", required: true },
       { name: "reason", type: "string", description: "Reason for dispute", required: true },
+      { name: "category", type: "DisputeCategory", description: "NonDelivery | Quality | MissedDeadline | ScopeDisagreement | ClientUnresponsive", required: true },
+      { name: "claimed_remedy", type: "ClaimedRemedy", description: "FullRefund | Rework | PartialRefund { amount: U128 }. Rulings cannot refund more than this", required: true },
     ],
     returns: "void",
     example: `await account.functionCall({
@@ -258,7 +269,9 @@ export const CHANGE_METHODS: ApiMethodData[] = [
   args: {
     contract_id: "c1",
     milestone_id: "m1",
-    reason: "Work does not meet requirements"
+    reason: "Work does not meet requirements",
+    category: "Quality",
+    claimed_remedy: { PartialRefund: { amount: "3000000000000000000000000" } }
   },
   gas: "300000000000000"
 });`,
@@ -150,10 +150,10 @@ npm install`}
                   { name: "fund_contract", desc: "Fund a contract. Specify total amount in NEAR including security deposit.", params: ["contract_id: string", "amount: string (NEAR)"] },
                   { name: "join_contract", desc: "Join a Draft contract as freelancer using an invite token.", params: ["contract_id: string", "invite_token: string"] },
                   { name: "start_milestone", desc: "Start a milestone (freelancer only). Changes status to InProgress.", params: ["contract_id: string", "milestone_id: string"] },
-                  { name: "request_payment", desc: "Request payment after completing a milestone. Starts 48-hour approval window.", params: ["contract_id: string", "milestone_id: string"] },
+                  { name: "request_payment", desc: "Request payment after completing a milestone and record the deliverable's hash and URI. Starts 48-hour approval window.", params: ["contract_id: string", "milestone_id: string", "content_hash: string (hex SHA-256)", "uri: string", "note?: string"] },
                   { name: "cancel_payment_request", desc: "Cancel a payment request and return to InProgress.", params: ["contract_id: string", "milestone_id: string"] },
                   { name: "approve_milestone", desc: "Approve a milestone and release payment to freelancer (client only).", params: ["contract_id: string", "milestone_id: string"] },
-                  { name: "raise_dispute", desc: "Raise a dispute on a milestone (client only). Triggers AI arbitration in TEE.", params: ["contract_id: string", "milestone_id: string", "reason: string"] },
+                  { name: "raise_dispute", desc: "Raise a dispute on a milestone (client only). Triggers AI arbitration in TEE. Rulings never refund more than the claimed remedy.", params: ["contract_id: string", "milestone_id: string", "reason: string", "category: NonDelivery | Quality | MissedDeadline | ScopeDisagreement | ClientUnresponsive", "claimed_remedy: FullRefund | PartialRefund | Rework", "refund_amount?: string (NEAR)"] },
                 ].map((tool) => (
                   <div key={tool.name} className="border border-slate-800 rounded-xl bg-slate-900/50 p-5">
                     <div className="flex items-center gap-3 mb-2">
@@ -229,7 +229,10 @@ await mcp.callTool("approve_milestone", {
 await mcp.callTool("raise_dispute", {
   contract_id: result.contract_id,
   milestone_id: "m1",
-  reason: "Photos do not meet quality requirements"
+  reason: "Photos do not meet quality requirements",
+  category: "Quality",
+  claimed_remedy: "PartialRefund",
+  refund_amount: "2"
 });`}
               language="typescript"
               title="OpenClaw Agent Example"
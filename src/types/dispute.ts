@@ -1,4 +1,23 @@
-export type DisputeStatus = "Pending" | "AiResolved" | "Finalized";
+export type DisputeStatus =
+  | "Pending"
+  | "AiResolved"
+  | "Finalized"
+  | "Settled"
+  | "Withdrawn"
+  | "Escalated"
+  | "Appealed";
+
+export type DisputeCategory =
+  | "NonDelivery"
+  | "Quality"
+  | "MissedDeadline"
+  | "ScopeDisagreement"
+  | "ClientUnresponsive";
+
+export type ClaimedRemedy =
+  | "FullRefund"
+  | "Rework"
+  | { PartialRefund: { amount: string } };
 
 export type Resolution =
   | "Freelancer"
@@ -6,6 +25,27 @@ export type Resolution =
   | "ContinueWork"
   | { Split: { freelancer_pct: number } };
 
+export interface EvidenceCommitment {
+  submitted_by: string;
+  content_hash: number[];
+  locator: string;
+  submitted_at_ns: number;
+}
+
+export interface Statement {
+  author: string;
+  text: string | null;
+  content_hash: number[] | null;
+  filed_at_ns: number;
+}
+
+export interface ResolutionAcceptance {
+  client_accepted: boolean;
+  freelancer_accepted: boolean;
+  client_waived: boolean;
+  freelancer_waived: boolean;
+}
+
 export interface EvidenceFile {
   cid: string;
   fileName: string;
@@ -28,4 +68,10 @@ export interface Dispute {
   tee_signing_address: number[] | null;
   tee_text: string | null;
   funds_released: boolean;
+  evidence: EvidenceCommitment[];
+  evidence_deadline_ns: number | null;
+  statements: Statement[];
+  response_deadline_ns: number | null;
+  client_waived_response: boolean;
+  freelancer_waived_response: boolean;
 }
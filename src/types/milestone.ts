@@ -1,5 +1,11 @@
 export type MilestoneStatus = "NotFunded" | "Funded" | "InProgress" | "SubmittedForReview" | "Completed" | "Disputed";
 
+export interface DeliverableInput {
+  contentHash: number[];
+  uri: string;
+  note?: string;
+}
+
 export interface Milestone {
   id: string;
   title: string;
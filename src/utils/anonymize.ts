@@ -11,10 +11,11 @@ export function anonymizeDisputeContext(params: {
   };
   milestone: { title: string; description: string; amount: string };
   dispute: { raised_by: string; reason: string; explanation?: string | null };
+  statements?: Array<{ author: string; text: string | null }>;
   chatHistory?: Array<{ sender: string; content: string }>;
   evidence?: Array<{ fileName: string; content: string }>;
 }): string {
-  const { contract, milestone, dispute, chatHistory, evidence } = params;
+  const { contract, milestone, dispute, statements, chatHistory, evidence } = params;
 
   const isClientRaiser = dispute.raised_by === contract.client;
 
@@ -55,6 +56,15 @@ export function anonymizeDisputeContext(params: {
     lines.push(`Previous AI resolution: ${scrub(dispute.explanation)}`);
   }
 
+  if (statements?.length) {
+    lines.push(``, `--- Party Statements (filed on-chain) ---`);
+    for (const statement of statements) {
+      const role = statement.author === contract.client ? "Party A" : "Party B";
+      const text = statement.text ? scrub(statement.text) : "(kept off-chain, only its hash was filed)";
+      lines.push(`[${role}]: ${text}`);
+    }
+  }
+
   if (chatHistory?.length) {
     lines.push(``, `--- Chat History (anonymized) ---`);
     for (const msg of chatHistory) {
@@ -64,7 +74,7 @@ export function anonymizeDisputeContext(params: {
   }
 
   if (evidence?.length) {
-    lines.push(``, `--- Evidence Files (encrypted via NOVA, decrypted for analysis) ---`);
+    lines.push(``, `--- Evidence (committed on-chain, NOVA files decrypted for analysis) ---`);
     for (const file of evidence) {
       lines.push(`[File: ${file.fileName}]`, scrub(file.content), ``);
     }
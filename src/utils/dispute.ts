@@ -0,0 +1,7 @@
+import type { Dispute } from "@/types/dispute";
+
+/** Mirrors the contract: arbitration may start once the deadline passes or both parties waive. */
+export function isResponseWindowClosed(dispute: Dispute, nowNs = Date.now() * 1e6): boolean {
+  if (dispute.client_waived_response && dispute.freelancer_waived_response) return true;
+  return dispute.response_deadline_ns === null || nowNs >= dispute.response_deadline_ns;
+}